@@ -39,6 +39,12 @@ pub(crate) enum FuzzCommand {
             help = "Skip building the program before adding new fuzz test."
         )]
         skip_build: bool,
+        #[arg(
+            long,
+            required = false,
+            help = "Generate a flow for every instruction of the program instead of empty flows."
+        )]
+        flows: bool,
     },
     Run {
         #[arg(
@@ -58,6 +64,32 @@ pub(crate) enum FuzzCommand {
             help = "Master seed used for fuzzing, if not provided it will be generated randomly."
         )]
         seed: Option<String>,
+        #[arg(
+            short,
+            long,
+            required = false,
+            help = "Run the fuzzing for the given duration (for example 30m or 2h) instead of the number of iterations set in the fuzz test."
+        )]
+        duration: Option<String>,
+        #[arg(
+            short,
+            long,
+            required = false,
+            help = "Stop all fuzzing threads after the first failure (invariant failure or panic)."
+        )]
+        fail_fast: bool,
+        #[arg(
+            long,
+            required = false,
+            help = "Run the given number of fuzzer processes sharing crashes, corpus and metrics through a sync directory."
+        )]
+        workers: Option<usize>,
+        #[arg(
+            long,
+            required = false,
+            help = "Shared directory of a distributed fuzzing campaign, used to join the campaign from another machine (for example over a network filesystem)."
+        )]
+        sync_dir: Option<String>,
     },
     Debug {
         #[arg(
@@ -67,10 +99,40 @@ pub(crate) enum FuzzCommand {
         target: String,
         #[arg(
             required = true,
-            help = "Master seed of the desired fuzz template to execute."
+            help = "Seed of the iteration to debug, or path to a crash artifact (.fuzz-artifacts/<target>/crashes/crash_<seed>.json)."
+        )]
+        seed: String,
+    },
+    #[command(
+        about = "Minimize a failing iteration to the smallest sequence of flow calls which still fails with the same panic."
+    )]
+    Minimize {
+        #[arg(
+            required = true,
+            help = "Name of the desired fuzz template to execute (for example fuzz_0)."
+        )]
+        target: String,
+        #[arg(
+            required = true,
+            help = "Seed of the failing iteration, or path to a crash artifact (.fuzz-artifacts/<target>/crashes/crash_<seed>.json)."
         )]
         seed: String,
     },
+    #[command(
+        about = "Replay the flow selections and random values recorded for a failing iteration."
+    )]
+    Replay {
+        #[arg(
+            required = true,
+            help = "Name of the desired fuzz template to execute (for example fuzz_0)."
+        )]
+        target: String,
+        #[arg(
+            required = true,
+            help = "Path to a replay file (.fuzz-artifacts/<target>/crashes/replay_<seed>.json)."
+        )]
+        replay_file: String,
+    },
     Refresh {
         #[arg(
             required = true,
@@ -106,25 +168,53 @@ pub(crate) async fn fuzz(subcmd: FuzzCommand) {
             target,
             with_exit_code,
             seed,
+            duration,
+            fail_fast,
+            workers,
+            sync_dir,
         } => {
             let commander = Commander::new(&root);
 
-            commander.run(target, with_exit_code, seed).await?;
+            commander
+                .run(
+                    target,
+                    with_exit_code,
+                    seed,
+                    duration,
+                    fail_fast,
+                    workers,
+                    sync_dir,
+                )
+                .await?;
         }
         FuzzCommand::Debug { target, seed } => {
             let commander = Commander::new(&root);
 
             commander.run_debug(target, seed).await?;
         }
+        FuzzCommand::Minimize { target, seed } => {
+            let commander = Commander::new(&root);
+
+            commander.run_minimize(target, seed).await?;
+        }
+        FuzzCommand::Replay {
+            target,
+            replay_file,
+        } => {
+            let commander = Commander::new(&root);
+
+            commander.run_replay(target, replay_file).await?;
+        }
 
         FuzzCommand::Add {
             program_name,
             test_name,
             skip_build,
+            flows,
         } => {
             let test_name_snake = test_name.map(|name| name.to_snake_case());
 
-            let mut generator = TestGenerator::new_with_root(&root, skip_build)?;
+            let mut generator = TestGenerator::new_with_root(&root, skip_build, flows)?;
 
             if let Some(test_name) = &test_name_snake {
                 check_fuzz_test_exists(&root, test_name)?;
@@ -141,7 +231,7 @@ pub(crate) async fn fuzz(subcmd: FuzzCommand) {
         } => {
             check_fuzz_test_not_exists(&root, &target)?;
 
-            let mut generator = TestGenerator::new_with_root(&root, skip_build)?;
+            let mut generator = TestGenerator::new_with_root(&root, skip_build, false)?;
 
             generator.refresh_fuzz_test(target, program_name).await?;
         }
@@ -13,10 +13,11 @@ pub(crate) async fn init(
     skip_build: bool,
     program_name: Option<String>,
     test_name: Option<String>,
+    flows: bool,
 ) {
     let root = check_anchor_initialized()?;
 
-    let mut generator: TestGenerator = TestGenerator::new_with_root(&root, skip_build)?;
+    let mut generator: TestGenerator = TestGenerator::new_with_root(&root, skip_build, flows)?;
 
     let test_name_snake = test_name.map(|name| name.to_snake_case());
 
@@ -58,6 +58,12 @@ enum Command {
             value_name = "NAME"
         )]
         test_name: Option<String>,
+        #[arg(
+            long,
+            required = false,
+            help = "Generate a flow for every instruction of the program instead of empty flows."
+        )]
+        flows: bool,
     },
     #[command(
         about = "Run fuzz subcommands.",
@@ -67,6 +73,8 @@ enum Command {
         \n    trident fuzz add\
         \n    trident fuzz run fuzz_0\
         \n    trident fuzz debug \x1b[92m<FUZZ_TARGET>\x1b[0m \x1b[92m<SEED>\x1b[0m\
+        \n    trident fuzz minimize \x1b[92m<FUZZ_TARGET>\x1b[0m \x1b[92m<SEED>\x1b[0m\
+        \n    trident fuzz replay \x1b[92m<FUZZ_TARGET>\x1b[0m \x1b[92m<REPLAY_FILE>\x1b[0m\
         \n    trident fuzz refresh fuzz_0"
     )]
     Fuzz {
@@ -125,7 +133,8 @@ pub async fn start() {
             skip_build,
             program_name,
             test_name,
-        } => command::init(force, skip_build, program_name, test_name).await?,
+            flows,
+        } => command::init(force, skip_build, program_name, test_name, flows).await?,
         Command::Clean => command::clean().await?,
         Command::Server {
             directory,
@@ -1,9 +1,11 @@
 use crate::coverage::Coverage;
 use crate::coverage::NotificationType;
+use crate::utils::ensure_fuzz_test_artifacts_dir;
 use crate::utils::generate_unique_fuzz_filename;
 use fehler::throw;
 use fehler::throws;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use tokio::process::Command;
 use trident_config::coverage::Coverage as CoverageConfig;
 use trident_config::TridentConfig;
@@ -13,9 +15,52 @@ use super::Error;
 
 impl Commander {
     #[throws]
-    pub async fn run(&self, target: String, with_exit_code: bool, seed: Option<String>) {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run(
+        &self,
+        target: String,
+        with_exit_code: bool,
+        seed: Option<String>,
+        duration: Option<String>,
+        fail_fast: bool,
+        workers: Option<usize>,
+        sync_dir: Option<String>,
+    ) {
         let config = TridentConfig::new();
 
+        if fail_fast || config.get_fail_fast() {
+            std::env::set_var("TRIDENT_FUZZ_FAIL_FAST", "1");
+        }
+
+        // The command line duration takes precedence over Trident.toml
+        if let Some(duration) = duration.or_else(|| config.get_duration()) {
+            let parsed = humantime::parse_duration(&duration).map_err(|e| {
+                Error::Anyhow(anyhow::anyhow!(
+                    "Invalid fuzzing duration \"{}\": {}",
+                    duration,
+                    e
+                ))
+            })?;
+            std::env::set_var("TRIDENT_FUZZ_DURATION", parsed.as_secs().to_string());
+        }
+
+        if let Some(timeout) = config.get_iteration_timeout() {
+            let parsed = humantime::parse_duration(&timeout).map_err(|e| {
+                Error::Anyhow(anyhow::anyhow!(
+                    "Invalid iteration timeout \"{}\": {}",
+                    timeout,
+                    e
+                ))
+            })?;
+            std::env::set_var(
+                "TRIDENT_FUZZ_ITERATION_TIMEOUT",
+                parsed.as_millis().to_string(),
+            );
+            if config.get_exit_on_timeout() {
+                std::env::set_var("TRIDENT_FUZZ_EXIT_ON_TIMEOUT", "1");
+            }
+        }
+
         if config.get_metrics() {
             std::env::set_var("FUZZING_METRICS", "true");
 
@@ -63,8 +108,48 @@ impl Commander {
             );
         }
 
+        if config.get_guided() {
+            std::env::set_var("TRIDENT_FUZZ_GUIDED", "1");
+
+            let corpus_dir = ensure_fuzz_test_artifacts_dir(&target, "corpus")
+                .await
+                .map_err(|e| {
+                    Error::Anyhow(anyhow::anyhow!(
+                        "Failed to create fuzzing corpus directory: {:?}",
+                        e
+                    ))
+                })?;
+            std::env::set_var(
+                "TRIDENT_FUZZ_CORPUS",
+                corpus_dir.to_string_lossy().to_string(),
+            );
+        }
+
+        if config.get_adaptive_flows() {
+            std::env::set_var("TRIDENT_FUZZ_ADAPTIVE", "1");
+        }
+
+        Self::set_swarm(&config);
+
+        Self::set_crashes_dir(&target).await?;
+
         let coverage_config = config.get_coverage();
-        if coverage_config.get_enable() {
+        if workers.is_some_and(|workers| workers > 1) || sync_dir.is_some() {
+            if coverage_config.get_enable() {
+                throw!(Error::Anyhow(anyhow::anyhow!(
+                    "Coverage is not supported with --workers or --sync-dir"
+                )));
+            }
+            self.run_workers(
+                &target,
+                seed,
+                with_exit_code,
+                workers.unwrap_or(1),
+                sync_dir,
+                config.get_guided(),
+            )
+            .await?;
+        } else if coverage_config.get_enable() {
             self.run_with_coverage(&target, &config, coverage_config, seed, with_exit_code)
                 .await?;
         } else {
@@ -82,6 +167,97 @@ impl Commander {
         Self::handle_child(&mut child, with_exit_code).await?;
     }
 
+    /// Runs the given number of fuzzer processes sharing crash artifacts, the corpus and
+    /// their fuzzing data through the sync directory, then merges the fuzzing data
+    #[throws]
+    async fn run_workers(
+        &self,
+        target: &str,
+        seed: Option<String>,
+        with_exit_code: bool,
+        workers: usize,
+        sync_dir: Option<String>,
+        guided: bool,
+    ) {
+        let sync_dir = match sync_dir {
+            Some(sync_dir) => PathBuf::from(sync_dir),
+            None => {
+                let sync_dir = ensure_fuzz_test_artifacts_dir(target, "sync")
+                    .await
+                    .map_err(|e| {
+                        Error::Anyhow(anyhow::anyhow!(
+                            "Failed to create fuzzing sync directory: {:?}",
+                            e
+                        ))
+                    })?;
+                // The fuzzing data of previous campaigns must not be merged again
+                let workers_dir = sync_dir.join("workers");
+                if workers_dir.exists() {
+                    tokio::fs::remove_dir_all(&workers_dir).await?;
+                }
+                sync_dir
+            }
+        };
+
+        let crashes_dir = sync_dir.join("crashes");
+        tokio::fs::create_dir_all(&crashes_dir).await?;
+        std::env::set_var(
+            "TRIDENT_FUZZ_CRASHES",
+            crashes_dir.to_string_lossy().to_string(),
+        );
+
+        if guided {
+            let corpus_dir = sync_dir.join("corpus");
+            tokio::fs::create_dir_all(&corpus_dir).await?;
+            std::env::set_var(
+                "TRIDENT_FUZZ_CORPUS",
+                corpus_dir.to_string_lossy().to_string(),
+            );
+        }
+
+        std::env::set_var(
+            "TRIDENT_FUZZ_SYNC_DIR",
+            sync_dir.to_string_lossy().to_string(),
+        );
+        std::env::set_var("TRIDENT_FUZZ_WORKERS", workers.to_string());
+
+        // Build the fuzz test once, so the workers do not wait for each other on the build lock
+        let status = Command::new("cargo")
+            .arg("build")
+            .arg("--bin")
+            .arg(target)
+            .args(["--profile", "release"])
+            .status()
+            .await?;
+        if !status.success() {
+            throw!(Error::FuzzingFailed);
+        }
+
+        let mut env_vars = HashMap::new();
+        if with_exit_code {
+            env_vars.insert("TRIDENT_WITH_EXIT_CODE", "1".to_string());
+        }
+
+        // Workers derive their seeds from one campaign seed and their index, so the
+        // campaign is reproduced by its seed
+        let seed = seed.unwrap_or_else(|| hex::encode(rand::random::<[u8; 32]>()));
+        println!("Campaign seed: {}", seed);
+
+        let mut children = Vec::with_capacity(workers);
+        for worker in 0..workers {
+            let mut env_vars = env_vars.clone();
+            env_vars.insert("TRIDENT_FUZZ_WORKER", worker.to_string());
+            children.push(self.spawn_fuzzer(target, env_vars, Some(seed.clone()))?);
+        }
+        if !Self::handle_workers(&mut children).await? {
+            return;
+        }
+
+        env_vars.insert("TRIDENT_FUZZ_MERGE", "1".to_string());
+        let mut child = self.spawn_fuzzer(target, env_vars, None)?;
+        Self::handle_child(&mut child, with_exit_code).await?;
+    }
+
     #[throws]
     pub async fn run_with_coverage(
         &self,
@@ -171,9 +347,86 @@ impl Commander {
             .spawn()?
     }
 
+    #[throws]
+    pub async fn run_minimize(&self, target: String, seed: String) {
+        Self::set_crashes_dir(&target).await?;
+        Self::set_swarm(&TridentConfig::new());
+
+        std::env::set_var("TRIDENT_FUZZ_MINIMIZE", seed);
+
+        let mut child = Command::new("cargo")
+            .arg("run")
+            .arg("--bin")
+            .arg(target)
+            .args(["--profile", "release"])
+            .spawn()?;
+
+        Self::handle_child(&mut child, false).await?;
+    }
+
+    #[throws]
+    pub async fn run_replay(&self, target: String, replay_file: String) {
+        let logs_name = std::path::Path::new(&replay_file)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| replay_file.clone());
+
+        let debug_path = generate_unique_fuzz_filename("trident_logs", &logs_name, "log")
+            .await
+            .map_err(|e| {
+                Error::Anyhow(anyhow::anyhow!(
+                    "Failed to generate debug fuzzing path: {:?}",
+                    e
+                ))
+            })?;
+
+        std::env::set_var(
+            "TRIDENT_FUZZ_DEBUG_PATH",
+            debug_path.to_string_lossy().to_string(),
+        );
+
+        // The replay is executed in debug mode so transaction logs are written
+        std::env::set_var("TRIDENT_FUZZ_DEBUG", &replay_file);
+        std::env::set_var("TRIDENT_FUZZ_REPLAY", replay_file);
+
+        let mut child = Command::new("cargo")
+            .arg("run")
+            .arg("--bin")
+            .arg(target)
+            .args(["--profile", "release"])
+            .spawn()?;
+
+        Self::handle_child(&mut child, false).await?;
+    }
+
+    /// Swarm mode is also enabled when debugging and minimizing,
+    /// so that seeds select the same flows as in the fuzzing session
+    fn set_swarm(config: &TridentConfig) {
+        if config.get_swarm_flows() {
+            std::env::set_var("TRIDENT_FUZZ_SWARM", "1");
+        }
+    }
+
+    #[throws]
+    async fn set_crashes_dir(target: &str) {
+        let crashes_dir = ensure_fuzz_test_artifacts_dir(target, "crashes")
+            .await
+            .map_err(|e| {
+                Error::Anyhow(anyhow::anyhow!(
+                    "Failed to create crash artifacts directory: {:?}",
+                    e
+                ))
+            })?;
+        std::env::set_var(
+            "TRIDENT_FUZZ_CRASHES",
+            crashes_dir.to_string_lossy().to_string(),
+        );
+    }
+
     #[throws]
     pub async fn run_debug(&self, target: String, seed: String) {
         let config = TridentConfig::new();
+        Self::set_swarm(&config);
 
         if config.get_metrics() {
             if config.get_metrics_json() {
@@ -222,7 +475,13 @@ impl Commander {
             println!("FUZZING_REGRESSION: {}", regression_path.to_string_lossy());
         }
 
-        let debug_path = generate_unique_fuzz_filename("trident_logs", &seed, "log")
+        // The seed can also be a path to a crash artifact, name the logs after the artifact then
+        let logs_name = std::path::Path::new(&seed)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| seed.clone());
+
+        let debug_path = generate_unique_fuzz_filename("trident_logs", &logs_name, "log")
             .await
             .map_err(|e| {
                 Error::Anyhow(anyhow::anyhow!(
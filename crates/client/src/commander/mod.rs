@@ -140,6 +140,44 @@ impl Commander {
             },
         }
     }
+
+    /// Waits for the worker processes of a distributed fuzzing campaign.
+    ///
+    /// Workers exit with 99 if they found failing invariants or panics and with 98 if they
+    /// found only timeouts or stopped on one, these are reported once the fuzzing data of
+    /// the workers is merged.
+    ///
+    /// # Returns
+    /// * `false` if the campaign was interrupted by Ctrl+C, which is delivered to the workers as well.
+    ///
+    /// # Errors
+    /// * Throws `Error::FuzzingFailed` if any worker failed for some other reason.
+    #[throws]
+    async fn handle_workers(children: &mut [Child]) -> bool {
+        let mut failed = false;
+        let mut interrupted = false;
+        for child in children.iter_mut() {
+            tokio::select! {
+                res = child.wait() => failed |= !matches!(res?.code(), Some(0) | Some(98) | Some(99)),
+                _ = signal::ctrl_c() => {
+                    interrupted = true;
+                    break;
+                }
+            }
+        }
+        if interrupted {
+            for child in children.iter_mut() {
+                let _res = child.wait().await?;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+            return false;
+        }
+        if failed {
+            throw!(Error::FuzzingFailed);
+        }
+        true
+    }
+
     #[throws]
     pub async fn clean_target(&self) {
         self.clean_anchor_target().await?;
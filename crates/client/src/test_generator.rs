@@ -11,6 +11,7 @@ use trident_template::TridentTemplates;
 pub struct TestGenerator {
     pub(crate) root: PathBuf,
     pub(crate) skip_build: bool,
+    pub(crate) generate_flows: bool,
     pub(crate) anchor_idls: Vec<Idl>,
     pub(crate) template_engine: TridentTemplates,
     pub(crate) generated_files: Option<GeneratedFiles>,
@@ -18,10 +19,11 @@ pub struct TestGenerator {
 
 impl TestGenerator {
     #[throws]
-    pub fn new_with_root(root: &str, skip_build: bool) -> Self {
+    pub fn new_with_root(root: &str, skip_build: bool, generate_flows: bool) -> Self {
         Self {
             root: Path::new(&root).to_path_buf(),
             skip_build,
+            generate_flows,
             anchor_idls: Vec::default(),
             template_engine: TridentTemplates::new()?,
             generated_files: None,
@@ -74,9 +76,11 @@ impl TestGenerator {
         let current_package_version = env!("CARGO_PKG_VERSION");
 
         // Generate templates using Tera
-        let output = self
-            .template_engine
-            .generate(&self.anchor_idls, current_package_version)?;
+        let output = self.template_engine.generate(
+            &self.anchor_idls,
+            current_package_version,
+            self.generate_flows,
+        )?;
 
         // Store the generated output
         self.generated_files = Some(output);
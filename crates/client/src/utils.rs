@@ -81,6 +81,17 @@ pub async fn ensure_fuzz_artifacts_dir() -> PathBuf {
     artifacts_dir
 }
 
+/// Creates .fuzz-artifacts/<fuzz_test_name>/<dir_name> directory if it doesn't exist
+#[throws]
+pub async fn ensure_fuzz_test_artifacts_dir(fuzz_test_name: &str, dir_name: &str) -> PathBuf {
+    let dir = ensure_fuzz_artifacts_dir()
+        .await?
+        .join(fuzz_test_name)
+        .join(dir_name);
+    create_directory_all(&dir).await?;
+    dir
+}
+
 /// Generates a unique filename in .fuzz-artifacts directory
 /// If the base filename already exists, appends a readable timestamp to make it unique
 #[throws]
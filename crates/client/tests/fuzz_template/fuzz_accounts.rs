@@ -6,7 +6,7 @@ use trident_fuzz::fuzzing::*;
 /// enabling their reuse across different instruction flows and test scenarios.
 ///
 /// Docs: https://ackee.xyz/trident/docs/latest/trident-api-macro/trident-types/fuzz-accounts/
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct AccountAddresses {
     pub composite_account_nested: AddressStorage,
 
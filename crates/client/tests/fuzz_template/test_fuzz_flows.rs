@@ -0,0 +1,312 @@
+use fuzz_accounts::*;
+use trident_fuzz::fuzzing::*;
+mod fuzz_accounts;
+mod types;
+use types::*;
+
+#[derive(FuzzTestMethods)]
+struct FuzzTest {
+    /// Trident client for interacting with the Solana program
+    trident: Trident,
+    /// Storage for all account addresses used in fuzz testing
+    fuzz_accounts: AccountAddresses,
+}
+
+#[flow_executor]
+impl FuzzTest {
+    fn new() -> Self {
+        Self {
+            trident: Trident::default(),
+            fuzz_accounts: AccountAddresses::default(),
+        }
+    }
+
+    #[init]
+    fn start(&mut self) {
+        // Perform any initialization here, this method will be executed
+        // at the start of each iteration
+    }
+
+    #[flow]
+    fn flow_additional_program_initialize(&mut self) {
+        let data = additional_program::InitializeInstructionData::random(&mut self.trident);
+
+        let ix = additional_program::InitializeInstruction::data(data)
+            .accounts(additional_program::InitializeInstructionAccounts::new())
+            .instruction();
+
+        self.trident.process_transaction(&[ix], Some("initialize"));
+    }
+
+    #[flow]
+    fn flow_idl_test_process_custom_types(&mut self) {
+        let data = idl_test::ProcessCustomTypesInstructionData::random(&mut self.trident);
+
+        let some_account = match self.fuzz_accounts.some_account.get(&mut self.trident) {
+            Some(some_account) => some_account,
+            None => self
+                .fuzz_accounts
+                .some_account
+                .insert(&mut self.trident, None),
+        };
+
+        let signer = match self.fuzz_accounts.signer.get(&mut self.trident) {
+            Some(signer) => signer,
+            None => {
+                let signer = self.fuzz_accounts.signer.insert(&mut self.trident, None);
+                self.trident.airdrop(&signer, 10 * LAMPORTS_PER_SOL);
+                signer
+            }
+        };
+
+        let data_account_1 = match self.fuzz_accounts.data_account_1.get(&mut self.trident) {
+            Some(data_account_1) => data_account_1,
+            None => self
+                .fuzz_accounts
+                .data_account_1
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_2 = match self.fuzz_accounts.data_account_2.get(&mut self.trident) {
+            Some(data_account_2) => data_account_2,
+            None => self
+                .fuzz_accounts
+                .data_account_2
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_3 = match self.fuzz_accounts.data_account_3.get(&mut self.trident) {
+            Some(data_account_3) => data_account_3,
+            None => self
+                .fuzz_accounts
+                .data_account_3
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_4 = match self.fuzz_accounts.data_account_4.get(&mut self.trident) {
+            Some(data_account_4) => data_account_4,
+            None => self
+                .fuzz_accounts
+                .data_account_4
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_5 = match self.fuzz_accounts.data_account_5.get(&mut self.trident) {
+            Some(data_account_5) => data_account_5,
+            None => self
+                .fuzz_accounts
+                .data_account_5
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_6 = match self.fuzz_accounts.data_account_6.get(&mut self.trident) {
+            Some(data_account_6) => data_account_6,
+            None => self
+                .fuzz_accounts
+                .data_account_6
+                .insert(&mut self.trident, None),
+        };
+
+        let ix = idl_test::ProcessCustomTypesInstruction::data(data)
+            .accounts(idl_test::ProcessCustomTypesInstructionAccounts::new(
+                idl_test::CompositeAccountNestedInstructionAccounts::new(
+                    some_account,
+                    idl_test::NestedInnerInstructionAccounts::new(some_account),
+                ),
+                signer,
+                data_account_1,
+                data_account_2,
+                data_account_3,
+                data_account_4,
+                data_account_5,
+                data_account_6,
+                idl_test::CompositeAccountInstructionAccounts::new(
+                    some_account,
+                    signer,
+                    data_account_1,
+                ),
+            ))
+            .instruction();
+
+        self.trident
+            .process_transaction(&[ix], Some("process_custom_types"));
+    }
+
+    #[flow]
+    fn flow_idl_test_process_rust_types(&mut self) {
+        let data = idl_test::ProcessRustTypesInstructionData::random(&mut self.trident);
+
+        let some_account = match self.fuzz_accounts.some_account.get(&mut self.trident) {
+            Some(some_account) => some_account,
+            None => self
+                .fuzz_accounts
+                .some_account
+                .insert(&mut self.trident, None),
+        };
+
+        let signer = match self.fuzz_accounts.signer.get(&mut self.trident) {
+            Some(signer) => signer,
+            None => {
+                let signer = self.fuzz_accounts.signer.insert(&mut self.trident, None);
+                self.trident.airdrop(&signer, 10 * LAMPORTS_PER_SOL);
+                signer
+            }
+        };
+
+        let data_account_1 = match self.fuzz_accounts.data_account_1.get(&mut self.trident) {
+            Some(data_account_1) => data_account_1,
+            None => self
+                .fuzz_accounts
+                .data_account_1
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_2 = match self.fuzz_accounts.data_account_2.get(&mut self.trident) {
+            Some(data_account_2) => data_account_2,
+            None => self
+                .fuzz_accounts
+                .data_account_2
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_3 = match self.fuzz_accounts.data_account_3.get(&mut self.trident) {
+            Some(data_account_3) => data_account_3,
+            None => self
+                .fuzz_accounts
+                .data_account_3
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_4 = match self.fuzz_accounts.data_account_4.get(&mut self.trident) {
+            Some(data_account_4) => data_account_4,
+            None => self
+                .fuzz_accounts
+                .data_account_4
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_5 = match self.fuzz_accounts.data_account_5.get(&mut self.trident) {
+            Some(data_account_5) => data_account_5,
+            None => self
+                .fuzz_accounts
+                .data_account_5
+                .insert(&mut self.trident, None),
+        };
+
+        let data_account_6 = match self.fuzz_accounts.data_account_6.get(&mut self.trident) {
+            Some(data_account_6) => data_account_6,
+            None => self
+                .fuzz_accounts
+                .data_account_6
+                .insert(&mut self.trident, None),
+        };
+
+        let ix = idl_test::ProcessRustTypesInstruction::data(data)
+            .accounts(idl_test::ProcessRustTypesInstructionAccounts::new(
+                idl_test::CompositeAccountNestedInstructionAccounts::new(
+                    some_account,
+                    idl_test::NestedInnerInstructionAccounts::new(some_account),
+                ),
+                signer,
+                data_account_1,
+                data_account_2,
+                data_account_3,
+                data_account_4,
+                data_account_5,
+                data_account_6,
+                idl_test::CompositeAccountInstructionAccounts::new(
+                    some_account,
+                    signer,
+                    data_account_1,
+                ),
+            ))
+            .instruction();
+
+        self.trident
+            .process_transaction(&[ix], Some("process_rust_types"));
+    }
+
+    #[flow]
+    fn flow_pda_program_initialize(&mut self) {
+        let data = pda_program::InitializeInstructionData::random(&mut self.trident);
+
+        let author = match self.fuzz_accounts.author.get(&mut self.trident) {
+            Some(author) => author,
+            None => {
+                let author = self.fuzz_accounts.author.insert(&mut self.trident, None);
+                self.trident.airdrop(&author, 10 * LAMPORTS_PER_SOL);
+                author
+            }
+        };
+
+        let vault = self.fuzz_accounts.vault.insert(
+            &mut self.trident,
+            Some(PdaSeeds::new(
+                &[b"vault", author.as_ref(), &data.vault_id.to_le_bytes()],
+                pda_program::program_id(),
+            )),
+        );
+
+        let ix = pda_program::InitializeInstruction::data(data)
+            .accounts(pda_program::InitializeInstructionAccounts::new(
+                author, vault,
+            ))
+            .instruction();
+
+        self.trident.process_transaction(&[ix], Some("initialize"));
+    }
+
+    #[flow]
+    fn flow_pda_program_deposit(&mut self) {
+        let data = pda_program::DepositInstructionData::random(&mut self.trident);
+
+        let author = match self.fuzz_accounts.author.get(&mut self.trident) {
+            Some(author) => author,
+            None => {
+                let author = self.fuzz_accounts.author.insert(&mut self.trident, None);
+                self.trident.airdrop(&author, 10 * LAMPORTS_PER_SOL);
+                author
+            }
+        };
+
+        let vault = self.fuzz_accounts.vault.insert(
+            &mut self.trident,
+            Some(PdaSeeds::new(
+                &[b"vault", author.as_ref(), &data.vault_id.to_le_bytes()],
+                pda_program::program_id(),
+            )),
+        );
+
+        let receipt = self.fuzz_accounts.receipt.insert(
+            &mut self.trident,
+            Some(PdaSeeds::new(
+                &[&[1u8, 2u8, 3u8], vault.as_ref(), data.memo.as_bytes()],
+                pda_program::program_id(),
+            )),
+        );
+
+        // Seeds of config could not be derived from the IDL
+        let config = match self.fuzz_accounts.config.get(&mut self.trident) {
+            Some(config) => config,
+            None => self.fuzz_accounts.config.insert(&mut self.trident, None),
+        };
+
+        let ix = pda_program::DepositInstruction::data(data)
+            .accounts(pda_program::DepositInstructionAccounts::new(
+                receipt, vault, author, config,
+            ))
+            .instruction();
+
+        self.trident.process_transaction(&[ix], Some("deposit"));
+    }
+
+    #[end]
+    fn end(&mut self) {
+        // Perform any cleanup here, this method will be executed
+        // at the end of each iteration
+    }
+}
+
+fn main() {
+    FuzzTest::fuzz(1000, 100);
+}
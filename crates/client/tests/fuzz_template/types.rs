@@ -59,7 +59,7 @@ pub mod additional_program {
     }
 
     /// Instruction data for Initialize
-    #[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+    #[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
     pub struct InitializeInstructionData {}
 
     impl InitializeInstructionData {
@@ -231,7 +231,7 @@ pub mod idl_test {
     }
 
     /// Instruction data for ProcessCustomTypes
-    #[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+    #[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
     pub struct ProcessCustomTypesInstructionData {
         pub _input_classic: ClassicStruct,
 
@@ -497,7 +497,7 @@ pub mod idl_test {
     }
 
     /// Instruction data for ProcessRustTypes
-    #[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+    #[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
     pub struct ProcessRustTypesInstructionData {
         pub _input_u8: u8,
 
@@ -835,7 +835,7 @@ pub mod idl_test {
 // ============================================================================
 
 /// Custom struct: ClassicStruct
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct ClassicStruct {
     pub field1: u8,
 
@@ -857,7 +857,7 @@ impl ClassicStruct {
 }
 
 /// Custom struct: ClassicStructAccount
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct ClassicStructAccount {
     pub field1: u8,
 
@@ -879,7 +879,7 @@ impl ClassicStructAccount {
 }
 
 /// Custom struct: DataAccount
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct DataAccount {
     pub unit_struct: UnitStruct,
 
@@ -967,7 +967,7 @@ impl DataAccount {
 }
 
 /// Custom enum: DataEnum
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub enum DataEnum {
     Integer(i32),
 
@@ -979,7 +979,7 @@ pub enum DataEnum {
 }
 
 /// Custom struct: DefaultStruct
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct DefaultStruct {
     pub field1: u8,
 
@@ -1001,7 +1001,7 @@ impl DefaultStruct {
 }
 
 /// Custom enum: GenericEnum
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub enum GenericEnum {
     Value(T),
 
@@ -1009,7 +1009,7 @@ pub enum GenericEnum {
 }
 
 /// Custom struct: GenericStruct
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct GenericStruct {
     pub value: T,
 
@@ -1023,7 +1023,7 @@ impl GenericStruct {
 }
 
 /// Custom enum: MultiDataEnum
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub enum MultiDataEnum {
     Pair(i32, i32),
 
@@ -1033,7 +1033,7 @@ pub enum MultiDataEnum {
 }
 
 /// Custom enum: NamedFieldsEnum
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub enum NamedFieldsEnum {
     Point { x: f64, y: f64 },
 
@@ -1043,7 +1043,7 @@ pub enum NamedFieldsEnum {
 }
 
 /// Custom struct: NestedStruct
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct NestedStruct {
     pub inner: ClassicStruct,
 
@@ -1057,7 +1057,7 @@ impl NestedStruct {
 }
 
 /// Custom struct: NestedStructAccount
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct NestedStructAccount {
     pub inner: ClassicStructAccount,
 }
@@ -1069,7 +1069,7 @@ impl NestedStructAccount {
 }
 
 /// Custom struct: OptionalFields
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct OptionalFields {
     pub field1: Option<u8>,
 
@@ -1091,7 +1091,7 @@ impl OptionalFields {
 }
 
 /// Custom struct: OptionalFieldsAccount
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct OptionalFieldsAccount {
     pub field1: Option<u8>,
 
@@ -1113,7 +1113,7 @@ impl OptionalFieldsAccount {
 }
 
 /// Custom enum: SimpleEnum
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub enum SimpleEnum {
     Variant1,
 
@@ -1123,7 +1123,7 @@ pub enum SimpleEnum {
 }
 
 /// Custom struct: TupleStruct
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct TupleStruct {
     pub field_0: u8,
 
@@ -1145,7 +1145,7 @@ impl TupleStruct {
 }
 
 /// Custom struct: TupleStructAccount
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct TupleStructAccount {
     pub field_0: u8,
 
@@ -1167,15 +1167,15 @@ impl TupleStructAccount {
 }
 
 /// Custom struct: UnitStruct
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct UnitStruct {}
 
 /// Custom struct: UnitStructAccount
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub struct UnitStructAccount {}
 
 /// Custom enum: UnitVariants
-#[derive(Debug, BorshDeserialize, BorshSerialize, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, Clone, FuzzRandom)]
 pub enum UnitVariants {
     VariantA,
 
@@ -30,14 +30,22 @@ async fn test_test_fuzz_generation() {
     verify_test_fuzz(&templates).await?;
 }
 
+#[throws]
+#[tokio::test]
+async fn test_test_fuzz_flows_generation() {
+    let templates = setup_templates()?;
+    verify_test_fuzz_flows(&templates).await?;
+}
+
 // Helper function to generate templates with proper error handling
 fn generate_templates(
     templates: &TridentTemplates,
     idls: Vec<Idl>,
+    generate_flows: bool,
 ) -> anyhow::Result<trident_template::GeneratedFiles> {
     let current_package_version = env!("CARGO_PKG_VERSION");
     templates
-        .generate(&idls, current_package_version)
+        .generate(&idls, current_package_version, generate_flows)
         .map_err(|e| anyhow::anyhow!("Template generation failed: {}", e))
 }
 
@@ -48,7 +56,7 @@ async fn verify_types(templates: &TridentTemplates) {
         read_idl("idl_test.json")?,
     ];
 
-    let generated_files = generate_templates(templates, idls)?;
+    let generated_files = generate_templates(templates, idls, false)?;
 
     let generated_types = &generated_files.types;
     let expected_types_path = construct_path("fuzz_template/types.rs");
@@ -65,7 +73,7 @@ async fn verify_fuzz_accounts(templates: &TridentTemplates) {
         read_idl("idl_test.json")?,
     ];
 
-    let generated_files = generate_templates(templates, idls)?;
+    let generated_files = generate_templates(templates, idls, false)?;
 
     let generated_fuzz = &generated_files.fuzz_accounts;
     let expected_fuzz_path = construct_path("fuzz_template/fuzz_accounts.rs");
@@ -86,7 +94,7 @@ async fn verify_test_fuzz(templates: &TridentTemplates) {
         read_idl("idl_test.json")?,
     ];
 
-    let generated_files = generate_templates(templates, idls)?;
+    let generated_files = generate_templates(templates, idls, false)?;
 
     let generated_test_fuzz = &generated_files.test_fuzz;
     let expected_test_fuzz_path = construct_path("fuzz_template/test_fuzz.rs");
@@ -100,6 +108,28 @@ async fn verify_test_fuzz(templates: &TridentTemplates) {
     );
 }
 
+#[throws]
+async fn verify_test_fuzz_flows(templates: &TridentTemplates) {
+    let idls = vec![
+        read_idl("additional_program.json")?,
+        read_idl("idl_test.json")?,
+        read_idl("pda_program.json")?,
+    ];
+
+    let generated_files = generate_templates(templates, idls, true)?;
+
+    let generated_test_fuzz = &generated_files.test_fuzz;
+    let expected_test_fuzz_path = construct_path("fuzz_template/test_fuzz_flows.rs");
+    let expected_test_fuzz = fs::read_to_string(&expected_test_fuzz_path)?;
+    let formatted_test_fuzz = Commander::format_program_code_nightly(generated_test_fuzz).await?;
+
+    assert_str_eq!(
+        formatted_test_fuzz,
+        expected_test_fuzz,
+        "Test fuzz with flows does not match"
+    );
+}
+
 #[throws]
 fn read_idl(idl_name: &str) -> Idl {
     let current_dir = std::env::current_dir()?;
@@ -0,0 +1,6 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub(crate) struct AccountSubstitution {
+    pub(crate) enabled: Option<bool>,
+}
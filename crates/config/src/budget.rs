@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub(crate) struct Budget {
+    pub(crate) duration: Option<String>,
+    pub(crate) fail_fast: Option<bool>,
+    pub(crate) iteration_timeout: Option<String>,
+    pub(crate) exit_on_timeout: Option<bool>,
+}
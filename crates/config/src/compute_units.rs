@@ -0,0 +1,9 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub(crate) struct ComputeUnits {
+    pub(crate) budget: Option<u64>,
+    pub(crate) transactions: Option<HashMap<String, u64>>,
+}
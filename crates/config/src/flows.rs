@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub(crate) struct Flows {
+    pub(crate) adaptive: Option<bool>,
+    pub(crate) swarm: Option<bool>,
+}
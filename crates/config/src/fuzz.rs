@@ -1,7 +1,14 @@
+use crate::account_substitution::AccountSubstitution;
+use crate::budget::Budget;
+use crate::compute_units::ComputeUnits;
 use crate::coverage::Coverage;
+use crate::flows::Flows;
+use crate::guided::Guided;
+use crate::invariants::Invariants;
 use crate::metrics::Metrics;
 use crate::regression::Regression;
 use crate::utils::resolve_path;
+use crate::values::Values;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use serde::Deserialize;
@@ -9,6 +16,7 @@ use serde::Serialize;
 use solana_sdk::account::AccountSharedData;
 use solana_sdk::account::WritableAccount;
 use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
 use std::fs;
 use std::str::FromStr;
 
@@ -16,6 +24,13 @@ use std::str::FromStr;
 pub struct Fuzz {
     metrics: Option<Metrics>,
     regression: Option<Regression>,
+    guided: Option<Guided>,
+    budget: Option<Budget>,
+    compute_units: Option<ComputeUnits>,
+    values: Option<Values>,
+    flows: Option<Flows>,
+    account_substitution: Option<AccountSubstitution>,
+    invariants: Option<Invariants>,
     pub programs: Option<Vec<_FuzzProgram>>,
     pub accounts: Option<Vec<_FuzzAccount>>,
     pub coverage: Option<Coverage>,
@@ -50,6 +65,115 @@ impl Fuzz {
         }
     }
 
+    pub fn get_guided(&self) -> bool {
+        match self.guided.as_ref() {
+            Some(guided) => guided.enabled.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn get_duration(&self) -> Option<String> {
+        self.budget
+            .as_ref()
+            .and_then(|budget| budget.duration.clone())
+    }
+
+    pub fn get_iteration_timeout(&self) -> Option<String> {
+        self.budget
+            .as_ref()
+            .and_then(|budget| budget.iteration_timeout.clone())
+    }
+
+    pub fn get_exit_on_timeout(&self) -> bool {
+        match self.budget.as_ref() {
+            Some(budget) => budget.exit_on_timeout.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn get_fail_fast(&self) -> bool {
+        match self.budget.as_ref() {
+            Some(budget) => budget.fail_fast.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn get_boundary_probability(&self) -> f64 {
+        self.values
+            .as_ref()
+            .and_then(|values| values.boundary_probability)
+            .unwrap_or(0.0)
+    }
+
+    pub fn get_dictionary_probability(&self) -> f64 {
+        self.values
+            .as_ref()
+            .and_then(|values| values.dictionary_probability)
+            .unwrap_or(0.0)
+    }
+
+    pub fn get_dictionary(&self) -> Vec<String> {
+        self.values
+            .as_ref()
+            .and_then(|values| values.dictionary.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_auto_dictionary(&self) -> bool {
+        match self.values.as_ref() {
+            Some(values) => values.auto_dictionary.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn get_compute_unit_budget(&self) -> Option<u64> {
+        self.compute_units
+            .as_ref()
+            .and_then(|compute_units| compute_units.budget)
+    }
+
+    pub fn get_transaction_compute_unit_budgets(&self) -> HashMap<String, u64> {
+        self.compute_units
+            .as_ref()
+            .and_then(|compute_units| compute_units.transactions.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_adaptive_flows(&self) -> bool {
+        match self.flows.as_ref() {
+            Some(flows) => flows.adaptive.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn get_swarm_flows(&self) -> bool {
+        match self.flows.as_ref() {
+            Some(flows) => flows.swarm.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn get_account_substitution(&self) -> bool {
+        match self.account_substitution.as_ref() {
+            Some(account_substitution) => account_substitution.enabled.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn get_lamport_conservation(&self) -> bool {
+        match self.invariants.as_ref() {
+            Some(invariants) => invariants.lamport_conservation.unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn get_account_state(&self) -> bool {
+        match self.invariants.as_ref() {
+            Some(invariants) => invariants.account_state.unwrap_or(false),
+            None => false,
+        }
+    }
+
     pub fn get_coverage(&self) -> Coverage {
         self.coverage.clone().unwrap_or_default()
     }
@@ -60,6 +184,7 @@ pub struct _FuzzProgram {
     pub address: String,
     pub upgrade_authority: Option<String>,
     pub program: String,
+    pub candidate: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -73,6 +198,8 @@ pub struct FuzzProgram {
     pub address: Pubkey,
     pub upgrade_authority: Option<Pubkey>,
     pub data: Vec<u8>,
+    /// Candidate build of the program, fuzzed against `data` in differential mode
+    pub candidate: Option<Vec<u8>>,
 }
 
 impl From<&_FuzzProgram> for FuzzProgram {
@@ -90,6 +217,11 @@ impl From<&_FuzzProgram> for FuzzProgram {
         let program_data =
             fs::read(path).unwrap_or_else(|_| panic!("Failed to read file: {}", program_path));
 
+        let candidate_data = _f.candidate.as_ref().map(|candidate_path| {
+            fs::read(resolve_path(candidate_path))
+                .unwrap_or_else(|_| panic!("Failed to read file: {}", candidate_path))
+        });
+
         let pubkey = Pubkey::from_str(program_address)
             .unwrap_or_else(|_| panic!("Cannot parse the program address: {}", program_address));
 
@@ -97,6 +229,7 @@ impl From<&_FuzzProgram> for FuzzProgram {
             address: pubkey,
             upgrade_authority,
             data: program_data,
+            candidate: candidate_data,
         }
     }
 }
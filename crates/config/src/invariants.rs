@@ -0,0 +1,7 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub(crate) struct Invariants {
+    pub(crate) lamport_conservation: Option<bool>,
+    pub(crate) account_state: Option<bool>,
+}
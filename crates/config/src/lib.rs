@@ -1,6 +1,12 @@
+mod account_substitution;
+mod budget;
+mod compute_units;
 pub mod constants;
 pub mod coverage;
+mod flows;
 pub mod fuzz;
+mod guided;
+mod invariants;
 mod metrics;
 use constants::*;
 use coverage::*;
@@ -8,11 +14,13 @@ use fuzz::*;
 pub mod utils;
 
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 use std::io;
 use thiserror::Error;
 use utils::discover_root;
 mod regression;
+mod values;
 
 #[derive(Error, Debug)]
 pub enum Error {
@@ -77,6 +85,113 @@ impl TridentConfig {
             .unwrap_or_default()
     }
 
+    pub fn get_guided(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_guided())
+            .unwrap_or_default()
+    }
+
+    pub fn get_duration(&self) -> Option<String> {
+        self.fuzz.as_ref().and_then(|fuzz| fuzz.get_duration())
+    }
+
+    pub fn get_iteration_timeout(&self) -> Option<String> {
+        self.fuzz
+            .as_ref()
+            .and_then(|fuzz| fuzz.get_iteration_timeout())
+    }
+
+    pub fn get_exit_on_timeout(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_exit_on_timeout())
+            .unwrap_or_default()
+    }
+
+    pub fn get_fail_fast(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_fail_fast())
+            .unwrap_or_default()
+    }
+
+    pub fn get_boundary_probability(&self) -> f64 {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_boundary_probability())
+            .unwrap_or_default()
+    }
+
+    pub fn get_dictionary_probability(&self) -> f64 {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_dictionary_probability())
+            .unwrap_or_default()
+    }
+
+    pub fn get_dictionary(&self) -> Vec<String> {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_dictionary())
+            .unwrap_or_default()
+    }
+
+    pub fn get_auto_dictionary(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_auto_dictionary())
+            .unwrap_or_default()
+    }
+
+    pub fn get_compute_unit_budget(&self) -> Option<u64> {
+        self.fuzz
+            .as_ref()
+            .and_then(|fuzz| fuzz.get_compute_unit_budget())
+    }
+
+    pub fn get_transaction_compute_unit_budgets(&self) -> HashMap<String, u64> {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_transaction_compute_unit_budgets())
+            .unwrap_or_default()
+    }
+
+    pub fn get_adaptive_flows(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_adaptive_flows())
+            .unwrap_or_default()
+    }
+
+    pub fn get_swarm_flows(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_swarm_flows())
+            .unwrap_or_default()
+    }
+
+    pub fn get_account_substitution(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_account_substitution())
+            .unwrap_or_default()
+    }
+
+    pub fn get_lamport_conservation(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_lamport_conservation())
+            .unwrap_or_default()
+    }
+
+    pub fn get_account_state(&self) -> bool {
+        self.fuzz
+            .as_ref()
+            .map(|fuzz| fuzz.get_account_state())
+            .unwrap_or_default()
+    }
+
     pub fn get_coverage(&self) -> Coverage {
         self.fuzz
             .as_ref()
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub(crate) struct Values {
+    pub(crate) boundary_probability: Option<f64>,
+    pub(crate) dictionary_probability: Option<f64>,
+    pub(crate) dictionary: Option<Vec<String>>,
+    pub(crate) auto_dictionary: Option<bool>,
+}
@@ -26,6 +26,13 @@ pub fn end(_attr: TokenStream, item: TokenStream) -> TokenStream {
     quote::quote!(#input_fn).into()
 }
 
+/// Marks a method to be checked after the init method and after every flow method
+#[proc_macro_attribute]
+pub fn invariant(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    quote::quote!(#input_fn).into()
+}
+
 /// Implements the flow executor for a struct
 #[proc_macro_attribute]
 pub fn flow_executor(_attr: TokenStream, item: TokenStream) -> TokenStream {
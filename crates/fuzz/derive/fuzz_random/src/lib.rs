@@ -0,0 +1,15 @@
+use proc_macro::TokenStream;
+use quote::ToTokens;
+use syn::parse_macro_input;
+use syn::DeriveInput;
+use trident_syn::parser::trident_fuzz_random::parse_trident_fuzz_random;
+
+#[proc_macro_derive(FuzzRandom, attributes(fuzz_random))]
+pub fn derive_fuzz_random(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match parse_trident_fuzz_random(&input) {
+        Ok(fuzz_random) => fuzz_random.to_token_stream().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -7,6 +7,7 @@ use crate::trident::Trident;
 ///
 /// `AddressStorage` provides a convenient way to store and retrieve addresses during fuzz testing.
 /// It can generate random addresses or derive PDAs, and allows you to randomly select from stored addresses.
+#[derive(Clone)]
 pub struct AddressStorage {
     addresses: Vec<Pubkey>,
 }
@@ -81,6 +82,7 @@ impl AddressStorage {
     pub fn insert(&mut self, trident: &mut Trident, seeds: Option<PdaSeeds>) -> Pubkey {
         let address = self.get_or_create_address(seeds, trident);
         self.addresses.push(address);
+        trident.record_stored_address(address);
         address
     }
 
@@ -0,0 +1,201 @@
+use solana_sdk::pubkey::Pubkey;
+
+use crate::trident::Trident;
+
+/// Maximal length of randomly generated strings and vectors, unless overridden
+/// by `#[fuzz_random(len = ..)]`
+pub const MAX_RANDOM_LEN: usize = 16;
+
+/// Types whose values can be generated randomly by Trident
+///
+/// Implemented for integers, floats, `bool`, `Pubkey`, `String`, `Option`, `Vec`,
+/// fixed-size arrays and `Box`, and derived by `#[derive(FuzzRandom)]` for structs and enums
+/// whose fields implement it. Every random value is drawn through `Trident`, so the value
+/// is reproducible by the iteration seed and recorded in replay files.
+///
+/// # Example
+/// ```rust,ignore
+/// #[derive(FuzzRandom)]
+/// pub struct DepositInstructionData {
+///     #[fuzz_random(range = 1..=1_000_000)]
+///     pub amount: u64,
+///     #[fuzz_random(len = 1..=4)]
+///     pub memo: Vec<u8>,
+///     pub referrer: Option<Pubkey>,
+/// }
+///
+/// let data = DepositInstructionData::random(&mut self.trident);
+/// ```
+pub trait FuzzRandom: Sized {
+    /// Generates a random value
+    fn random(trident: &mut Trident) -> Self;
+}
+
+macro_rules! impl_fuzz_random_int {
+    ($($t:ty),*) => {$(
+        impl FuzzRandom for $t {
+            fn random(trident: &mut Trident) -> Self {
+                trident.random_biased_from_range(<$t>::MIN..=<$t>::MAX)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_fuzz_random_float {
+    ($($t:ty),*) => {$(
+        impl FuzzRandom for $t {
+            fn random(trident: &mut Trident) -> Self {
+                // Wider ranges overflow the scale of the uniform float sampling
+                trident.random_biased_from_range(<$t>::MIN / 4.0..=<$t>::MAX / 4.0)
+            }
+        }
+    )*};
+}
+
+impl_fuzz_random_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_fuzz_random_float!(f32, f64);
+
+impl FuzzRandom for bool {
+    fn random(trident: &mut Trident) -> Self {
+        trident.random_bool()
+    }
+}
+
+impl FuzzRandom for Pubkey {
+    fn random(trident: &mut Trident) -> Self {
+        trident.random_pubkey()
+    }
+}
+
+/// Collections whose length can be set by `#[fuzz_random(len = ..)]`
+pub trait FuzzRandomLen: Sized {
+    /// Generates a random value with the given length
+    fn random_with_len(trident: &mut Trident, len: usize) -> Self;
+}
+
+impl FuzzRandom for String {
+    fn random(trident: &mut Trident) -> Self {
+        let len = trident.random_from_range(0..=MAX_RANDOM_LEN);
+        Self::random_with_len(trident, len)
+    }
+}
+
+impl FuzzRandomLen for String {
+    fn random_with_len(trident: &mut Trident, len: usize) -> Self {
+        trident.random_string(len)
+    }
+}
+
+impl<T: FuzzRandom> FuzzRandom for Option<T> {
+    fn random(trident: &mut Trident) -> Self {
+        trident.random_bool().then(|| T::random(trident))
+    }
+}
+
+impl<T: FuzzRandom> FuzzRandom for Vec<T> {
+    fn random(trident: &mut Trident) -> Self {
+        let len = trident.random_from_range(0..=MAX_RANDOM_LEN);
+        Self::random_with_len(trident, len)
+    }
+}
+
+impl<T: FuzzRandom> FuzzRandomLen for Vec<T> {
+    fn random_with_len(trident: &mut Trident, len: usize) -> Self {
+        (0..len).map(|_| T::random(trident)).collect()
+    }
+}
+
+impl<T: FuzzRandom, const N: usize> FuzzRandom for [T; N] {
+    fn random(trident: &mut Trident) -> Self {
+        std::array::from_fn(|_| T::random(trident))
+    }
+}
+
+impl<T: FuzzRandom> FuzzRandom for Box<T> {
+    fn random(trident: &mut Trident) -> Self {
+        Box::new(T::random(trident))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use trident_derive_fuzz_random::FuzzRandom;
+
+    use super::*;
+
+    const DRAWS: usize = 200;
+
+    #[derive(FuzzRandom)]
+    struct Deposit {
+        #[fuzz_random(range = 1..=100)]
+        amount: u64,
+        #[fuzz_random(len = 1..4)]
+        memo: Vec<u8>,
+        #[fuzz_random(len = 2..=2)]
+        name: String,
+        referrer: Option<Pubkey>,
+    }
+
+    #[derive(FuzzRandom)]
+    enum Action {
+        Deposit {
+            #[fuzz_random(range = 10..20)]
+            amount: u64,
+        },
+        Withdraw(#[fuzz_random(range = -5..=5)] i64),
+        Close,
+    }
+
+    #[derive(FuzzRandom)]
+    struct Wrapper<T> {
+        values: [T; 4],
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut trident = Trident::for_tests();
+        for _ in 0..DRAWS {
+            let deposit = Deposit::random(&mut trident);
+            assert!((1..=100).contains(&deposit.amount));
+            assert!((1..4).contains(&deposit.memo.len()));
+            assert_eq!(deposit.name.len(), 2);
+            let _ = deposit.referrer;
+        }
+    }
+
+    #[test]
+    fn test_enum_selects_every_variant() {
+        let mut trident = Trident::for_tests();
+        let mut selected = [false; 3];
+        for _ in 0..DRAWS {
+            match Action::random(&mut trident) {
+                Action::Deposit { amount } => {
+                    assert!((10..20).contains(&amount));
+                    selected[0] = true;
+                }
+                Action::Withdraw(amount) => {
+                    assert!((-5..=5).contains(&amount));
+                    selected[1] = true;
+                }
+                Action::Close => selected[2] = true,
+            }
+        }
+        assert_eq!(selected, [true; 3]);
+    }
+
+    #[test]
+    fn test_generic_type() {
+        let mut trident = Trident::for_tests();
+        let wrapper = Wrapper::<bool>::random(&mut trident);
+        assert_eq!(wrapper.values.len(), 4);
+    }
+
+    #[test]
+    fn test_default_lengths() {
+        let mut trident = Trident::for_tests();
+        for _ in 0..DRAWS {
+            assert!(Vec::<u8>::random(&mut trident).len() <= MAX_RANDOM_LEN);
+            assert!(String::random(&mut trident).len() <= MAX_RANDOM_LEN);
+        }
+    }
+}
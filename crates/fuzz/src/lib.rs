@@ -1,5 +1,6 @@
 pub mod address_storage;
 pub mod error;
+pub mod fuzz_random;
 pub mod trident;
 pub mod trident_rng;
 
@@ -30,6 +31,8 @@ pub mod fuzzing {
     pub use trident_derive_flow_executor::flow;
     pub use trident_derive_flow_executor::flow_executor;
     pub use trident_derive_flow_executor::init;
+    pub use trident_derive_flow_executor::invariant;
+    pub use trident_derive_fuzz_random::FuzzRandom;
     pub use trident_derive_fuzz_test_methods::FuzzTestMethods;
 
     /// Trident svm
@@ -40,6 +43,10 @@ pub mod fuzzing {
     pub use trident_svm::types::trident_entrypoint::TridentEntrypoint;
     pub use trident_svm::types::trident_program::TridentProgram;
 
+    /// Random values
+    pub use super::fuzz_random::FuzzRandom;
+    pub use super::fuzz_random::FuzzRandomLen;
+
     /// Accounts storages
     pub use super::address_storage::AddressStorage;
     pub use super::address_storage::PdaSeeds;
@@ -49,6 +56,9 @@ pub mod fuzzing {
 
     /// Trident
     pub use super::trident::flow_executor::FlowExecutor;
+    #[doc(hidden)]
+    pub use super::trident::FlowCandidate;
+    pub use super::trident::RangeValue;
     pub use super::trident::Trident;
     pub use trident_fuzz_metrics::TridentFuzzingData;
 
@@ -0,0 +1,167 @@
+use solana_sdk::account::ReadableAccount;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signer::Signer;
+use trident_config::TridentConfig;
+use trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction;
+use trident_svm::prelude::TridentTransactionProcessingResult;
+
+use crate::trident::Trident;
+
+// The re-export is deprecated in favour of `solana-system-interface`, which is not a
+// dependency of the crate
+#[allow(deprecated)]
+const SYSTEM_PROGRAM: Pubkey = solana_sdk::system_program::ID;
+
+/// Account state invariants, enabled by `account_state` in `[fuzz.invariants]`
+///
+/// After every successful transaction, each writable account of the transaction must be
+/// rent-exempt for its data length, closed accounts must not keep any data and the owner
+/// of an account may only change when the account is created, assigned by the System
+/// Program or returned to it without data.
+pub(crate) struct AccountStateInvariants {
+    enabled: bool,
+}
+
+impl AccountStateInvariants {
+    pub(crate) fn new(config: &TridentConfig) -> Self {
+        Self {
+            enabled: config.get_account_state(),
+        }
+    }
+}
+
+/// State of a writable account before the transaction
+pub(crate) struct AccountStateBefore {
+    address: Pubkey,
+    lamports: u64,
+    data_len: usize,
+    owner: Pubkey,
+}
+
+impl Trident {
+    /// State of every writable account of the transaction, if the account state invariants
+    /// are enabled
+    pub(crate) fn account_states_before_transaction(
+        &self,
+        instructions: &[Instruction],
+    ) -> Option<Vec<AccountStateBefore>> {
+        if !self.account_state.enabled {
+            return None;
+        }
+        let message = Message::new(instructions, Some(&self.payer().pubkey()));
+        Some(
+            message
+                .account_keys
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| message.is_maybe_writable(*index, None))
+                .map(|(_, address)| {
+                    let account = self.client.get_account(address).unwrap_or_default();
+                    AccountStateBefore {
+                        address: *address,
+                        lamports: account.lamports(),
+                        data_len: account.data().len(),
+                        owner: *account.owner(),
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Account state invariants, records a finding of the transaction for every writable
+    /// account left in an invalid state
+    ///
+    /// Unlike panics of the fuzz test, the findings do not stop the iteration, same as
+    /// panics of programs.
+    pub(crate) fn check_account_states(
+        &mut self,
+        log_as: Option<&str>,
+        states_before: Option<Vec<AccountStateBefore>>,
+        processing_data: &TridentTransactionProcessingResult,
+    ) {
+        let Some(states_before) = states_before else {
+            return;
+        };
+        // Failed transactions do not change the accounts, except for the fee
+        let succeeded = matches!(
+            &processing_data.get_result().processing_results[0],
+            Ok(ProcessedTransaction::Executed(executed_transaction))
+                if executed_transaction.execution_details.status.is_ok()
+        );
+        if !succeeded {
+            return;
+        }
+
+        let rent = self.get_sysvar::<Rent>();
+        let mut violations = Vec::new();
+        for before in states_before {
+            let Some(after) = self.client.get_account(&before.address) else {
+                continue;
+            };
+            if after.executable() {
+                continue;
+            }
+            let data_len = after.data().len();
+
+            // Zeroed data cannot be mistaken for a live account if the account is revived
+            if after.lamports() == 0 {
+                let leftover = after.data().iter().filter(|byte| **byte != 0).count();
+                if leftover > 0 {
+                    violations.push(format!(
+                        "account {} closed with {} non-zero bytes of leftover data",
+                        before.address, leftover
+                    ));
+                }
+                continue;
+            }
+
+            let rent_paying = |lamports: u64, data_len: usize| {
+                lamports > 0 && !rent.is_exempt(lamports, data_len)
+            };
+            if rent_paying(after.lamports(), data_len)
+                && !rent_paying(before.lamports, before.data_len)
+            {
+                violations.push(format!(
+                    "account {} left with {} lamports below the rent-exempt minimum of {} lamports for {} bytes of data",
+                    before.address,
+                    after.lamports(),
+                    rent.minimum_balance(data_len),
+                    data_len
+                ));
+            }
+
+            let created = before.lamports == 0;
+            let assigned = before.owner == SYSTEM_PROGRAM;
+            let returned = *after.owner() == SYSTEM_PROGRAM && data_len == 0;
+            if *after.owner() != before.owner && !created && !assigned && !returned {
+                violations.push(format!(
+                    "owner of account {} changed from {} to {}",
+                    before.address,
+                    before.owner,
+                    after.owner()
+                ));
+            }
+        }
+
+        let transaction_name = log_as.unwrap_or("without name");
+        for violation in violations {
+            if std::env::var("TRIDENT_FUZZ_DEBUG").is_ok() {
+                trident_svm::prelude::trident_svm_log::log_message(
+                    &format!("ACCOUNT STATE VIOLATED: {}", violation),
+                    trident_svm::prelude::Level::Error,
+                );
+            }
+            let seed = self.rng.get_seed();
+            let flow_calls = self.iteration_flow_calls();
+            self.fuzzing_data.add_account_state_finding(
+                transaction_name,
+                &violation,
+                &seed,
+                flow_calls,
+            );
+        }
+    }
+}
@@ -0,0 +1,432 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::account::ReadableAccount;
+use solana_sdk::account::WritableAccount;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use trident_config::TridentConfig;
+use trident_fuzz_metrics::types::Seed;
+use trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction;
+
+use crate::trident::transaction_result::TransactionResult;
+use crate::trident::Trident;
+
+/// Account substitution mode, enabled by `[fuzz.account_substitution]`
+///
+/// Before a transaction is executed, one randomly chosen account meta of its instructions
+/// is mutated and the mutated transaction is executed without committing its changes.
+/// If both the mutated and the original transaction succeed, the program may miss
+/// a constraint (owner, signer, seeds or `has_one` check) on the account, which is
+/// recorded as a finding of the transaction. Only instructions of the programs in
+/// `[[fuzz.programs]]` are mutated, built-in programs accept e.g. swapped accounts by design.
+pub(crate) struct SubstitutionState {
+    enabled: bool,
+    programs: HashSet<Pubkey>,
+    /// Rng of the substitutions, derived from the iteration seed
+    ///
+    /// Substitutions do not draw from the rng of the fuzz test, so enabling them does not
+    /// change the values drawn by the flows nor the recorded replay files, while the same
+    /// iteration seed still substitutes the same accounts.
+    rng: Option<(Seed, SmallRng)>,
+    /// Addresses inserted into the address storages of the fuzz test, swapped in for
+    /// accounts of the transaction
+    stored_addresses: Vec<Pubkey>,
+}
+
+impl SubstitutionState {
+    pub(crate) fn new(config: &TridentConfig) -> Self {
+        Self {
+            enabled: config.get_account_substitution(),
+            programs: config
+                .programs()
+                .iter()
+                .map(|program| program.address)
+                .collect(),
+            rng: None,
+            stored_addresses: Vec::new(),
+        }
+    }
+}
+
+/// Mutation of an account meta
+#[derive(Debug, PartialEq)]
+enum Substitution {
+    /// Address of another account of the transaction or of the address storages
+    Swap(Pubkey),
+    /// Copy of the PDA at the given address, owned by an attacker program
+    AttackerOwned(Pubkey),
+    /// The account does not sign the transaction
+    DropSigner,
+    /// The account is passed as readonly
+    Readonly,
+}
+
+impl Substitution {
+    /// Mutates the account meta of the instruction
+    ///
+    /// # Returns
+    /// Description of the substitution
+    fn apply(&self, meta: &mut AccountMeta, instruction_index: usize) -> String {
+        let original = meta.pubkey;
+        match self {
+            Self::Swap(other) => {
+                meta.pubkey = *other;
+                format!(
+                    "account {} of instruction {} replaced by account {}",
+                    original, instruction_index, other
+                )
+            }
+            Self::AttackerOwned(address) => {
+                meta.pubkey = *address;
+                format!(
+                    "PDA {} of instruction {} replaced by a copy owned by an attacker program",
+                    original, instruction_index
+                )
+            }
+            Self::DropSigner => {
+                meta.is_signer = false;
+                format!(
+                    "account {} of instruction {} not signing the transaction",
+                    original, instruction_index
+                )
+            }
+            Self::Readonly => {
+                meta.is_writable = false;
+                format!(
+                    "account {} of instruction {} passed as readonly",
+                    original, instruction_index
+                )
+            }
+        }
+    }
+}
+
+/// Account metas which can be substituted, as indices of the instruction and of the meta
+///
+/// Only instructions of the fuzzed programs are mutated. The fee payer, the invoked
+/// programs and executable accounts cannot be substituted.
+fn substitution_targets(
+    instructions: &[Instruction],
+    programs: &HashSet<Pubkey>,
+    payer: &Pubkey,
+    is_executable: impl Fn(&Pubkey) -> bool,
+) -> Vec<(usize, usize)> {
+    let mut targets = Vec::new();
+    for (instruction_index, instruction) in instructions.iter().enumerate() {
+        if !programs.contains(&instruction.program_id) {
+            continue;
+        }
+        for (meta_index, meta) in instruction.accounts.iter().enumerate() {
+            if meta.pubkey == *payer
+                || instructions.iter().any(|ix| ix.program_id == meta.pubkey)
+                || is_executable(&meta.pubkey)
+            {
+                continue;
+            }
+            targets.push((instruction_index, meta_index));
+        }
+    }
+    targets
+}
+
+/// Substitutions applicable to the account meta
+///
+/// # Arguments
+/// * `swap` - Address to swap in for the account, if there is any other account
+/// * `attacker` - Address of the attacker-owned copy, if the account is an existing PDA
+fn applicable_substitutions(
+    meta: &AccountMeta,
+    swap: Option<Pubkey>,
+    attacker: Option<Pubkey>,
+) -> Vec<Substitution> {
+    let mut substitutions = Vec::new();
+    substitutions.extend(swap.map(Substitution::Swap));
+    substitutions.extend(attacker.map(Substitution::AttackerOwned));
+    if meta.is_signer {
+        substitutions.push(Substitution::DropSigner);
+    }
+    if meta.is_writable {
+        substitutions.push(Substitution::Readonly);
+    }
+    substitutions
+}
+
+impl Trident {
+    fn substitution_rng(&mut self) -> &mut SmallRng {
+        let seed = self.rng.get_seed();
+        let rng = self
+            .substitution
+            .rng
+            .take()
+            .filter(|(rng_seed, _)| *rng_seed == seed)
+            .unwrap_or_else(|| {
+                let mut hasher = DefaultHasher::new();
+                ("account_substitution", seed).hash(&mut hasher);
+                (seed, SmallRng::seed_from_u64(hasher.finish()))
+            });
+        &mut self.substitution.rng.insert(rng).1
+    }
+
+    /// Remembers an address inserted into an address storage of the fuzz test,
+    /// if account substitution is enabled
+    pub(crate) fn record_stored_address(&mut self, address: Pubkey) {
+        if self.substitution.enabled {
+            self.substitution.stored_addresses.push(address);
+        }
+    }
+
+    pub(crate) fn stored_addresses(&self) -> &[Pubkey] {
+        &self.substitution.stored_addresses
+    }
+
+    pub(crate) fn set_stored_addresses(&mut self, addresses: Vec<Pubkey>) {
+        self.substitution.stored_addresses = addresses;
+    }
+
+    /// Executes the transaction with one substituted account without committing its changes
+    ///
+    /// # Returns
+    /// Description of the substitution, if the substituted transaction succeeded
+    pub(crate) fn substitute_account(&mut self, instructions: &[Instruction]) -> Option<String> {
+        if !self.substitution.enabled {
+            return None;
+        }
+        let payer = self.payer().pubkey();
+
+        let targets = substitution_targets(
+            instructions,
+            &self.substitution.programs,
+            &payer,
+            |address| {
+                self.client
+                    .get_account(address)
+                    .is_some_and(|account| account.executable())
+            },
+        );
+        if targets.is_empty() {
+            return None;
+        }
+
+        let (instruction_index, meta_index) =
+            targets[self.substitution_rng().gen_range(0..targets.len())];
+        let meta = instructions[instruction_index].accounts[meta_index].clone();
+
+        let mut others: Vec<Pubkey> = targets
+            .iter()
+            .map(|&(i, m)| instructions[i].accounts[m].pubkey)
+            .chain(self.substitution.stored_addresses.iter().copied())
+            .filter(|address| *address != meta.pubkey && *address != payer)
+            .collect();
+        others.sort();
+        others.dedup();
+        let swap = (!others.is_empty())
+            .then(|| others[self.substitution_rng().gen_range(0..others.len())]);
+
+        let account = self.client.get_account(&meta.pubkey).unwrap_or_default();
+        let attacker = (!meta.pubkey.is_on_curve() && account.lamports() > 0)
+            .then(|| Pubkey::new_from_array(self.substitution_rng().gen()));
+
+        let mut substitutions = applicable_substitutions(&meta, swap, attacker);
+        if substitutions.is_empty() {
+            return None;
+        }
+        let substitution =
+            substitutions.swap_remove(self.substitution_rng().gen_range(0..substitutions.len()));
+
+        if let Substitution::AttackerOwned(address) = substitution {
+            let mut attacker_copy = account.clone();
+            attacker_copy.set_owner(Pubkey::new_from_array(self.substitution_rng().gen()));
+            self.client.set_account(&address, &attacker_copy, false);
+        }
+        let mut substituted = instructions.to_vec();
+        let description = substitution.apply(
+            &mut substituted[instruction_index].accounts[meta_index],
+            instruction_index,
+        );
+
+        // The substituted transaction must not advance the clock of the original one
+        let clock = self.client.get_sysvar::<Clock>();
+        let tx = Transaction::new_with_payer(&substituted, Some(&payer));
+        let processing_data = self.client.process_transaction(tx);
+        self.client.set_sysvar(&clock);
+        if let Substitution::AttackerOwned(address) = substitution {
+            self.client
+                .set_account(&address, &AccountSharedData::default(), false);
+        }
+
+        let succeeded = matches!(
+            &processing_data.get_result().processing_results[0],
+            Ok(ProcessedTransaction::Executed(executed_transaction))
+                if executed_transaction.execution_details.status.is_ok()
+        );
+        succeeded.then_some(description)
+    }
+
+    /// Records a finding of the transaction if the original transaction succeeded as well
+    /// as the transaction with the substituted account
+    ///
+    /// The substitution may be accepted by design (e.g. two interchangeable accounts), so
+    /// the finding does not stop the iteration.
+    pub(crate) fn check_account_substitution(
+        &mut self,
+        log_as: Option<&str>,
+        result: &TransactionResult,
+        substitution: Option<String>,
+    ) {
+        let Some(substitution) = substitution else {
+            return;
+        };
+        if !result.is_success() {
+            return;
+        }
+        let violation = format!(
+            "the transaction also succeeded with {}, the program may be missing a constraint on the account",
+            substitution
+        );
+        if std::env::var("TRIDENT_FUZZ_DEBUG").is_ok() {
+            trident_svm::prelude::trident_svm_log::log_message(
+                &format!("ACCOUNT SUBSTITUTION ACCEPTED: {}", violation),
+                trident_svm::prelude::Level::Error,
+            );
+        }
+        let seed = self.rng.get_seed();
+        let flow_calls = self.iteration_flow_calls();
+        self.fuzzing_data.add_account_substitution_finding(
+            log_as.unwrap_or("without name"),
+            &violation,
+            &seed,
+            flow_calls,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction(program_id: Pubkey, accounts: Vec<AccountMeta>) -> Instruction {
+        Instruction {
+            program_id,
+            accounts,
+            data: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_substitution_targets() {
+        let program = Pubkey::new_unique();
+        let builtin = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let executable = Pubkey::new_unique();
+        let (user, vault, other) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let instructions = [
+            instruction(
+                program,
+                vec![
+                    AccountMeta::new(payer, true),
+                    AccountMeta::new(user, true),
+                    AccountMeta::new_readonly(builtin, false),
+                    AccountMeta::new_readonly(executable, false),
+                    AccountMeta::new(vault, false),
+                ],
+            ),
+            // Instructions of other programs are never mutated
+            instruction(builtin, vec![AccountMeta::new(other, false)]),
+        ];
+
+        let targets = substitution_targets(
+            &instructions,
+            &HashSet::from([program]),
+            &payer,
+            |address| *address == executable,
+        );
+        assert_eq!(targets, vec![(0, 1), (0, 4)]);
+
+        let targets = substitution_targets(&instructions, &HashSet::new(), &payer, |_| false);
+        assert!(targets.is_empty());
+    }
+
+    #[test]
+    fn test_applicable_substitutions() {
+        let account = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+
+        assert_eq!(
+            applicable_substitutions(
+                &AccountMeta::new(account, true),
+                Some(other),
+                Some(attacker)
+            ),
+            vec![
+                Substitution::Swap(other),
+                Substitution::AttackerOwned(attacker),
+                Substitution::DropSigner,
+                Substitution::Readonly,
+            ]
+        );
+        assert_eq!(
+            applicable_substitutions(&AccountMeta::new_readonly(account, false), None, None),
+            vec![]
+        );
+        assert_eq!(
+            applicable_substitutions(&AccountMeta::new_readonly(account, true), None, None),
+            vec![Substitution::DropSigner]
+        );
+    }
+
+    #[test]
+    fn test_apply_swap() {
+        let (account, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut meta = AccountMeta::new(account, true);
+        let description = Substitution::Swap(other).apply(&mut meta, 1);
+        assert_eq!(meta, AccountMeta::new(other, true));
+        assert_eq!(
+            description,
+            format!(
+                "account {} of instruction 1 replaced by account {}",
+                account, other
+            )
+        );
+    }
+
+    #[test]
+    fn test_apply_attacker_owned() {
+        let (pda, attacker) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut meta = AccountMeta::new(pda, false);
+        let description = Substitution::AttackerOwned(attacker).apply(&mut meta, 0);
+        assert_eq!(meta, AccountMeta::new(attacker, false));
+        assert!(description.contains("owned by an attacker program"));
+    }
+
+    #[test]
+    fn test_apply_drop_signer() {
+        let account = Pubkey::new_unique();
+        let mut meta = AccountMeta::new(account, true);
+        Substitution::DropSigner.apply(&mut meta, 0);
+        assert_eq!(meta, AccountMeta::new(account, false));
+    }
+
+    #[test]
+    fn test_apply_readonly() {
+        let account = Pubkey::new_unique();
+        let mut meta = AccountMeta::new(account, true);
+        Substitution::Readonly.apply(&mut meta, 0);
+        assert_eq!(meta, AccountMeta::new_readonly(account, true));
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use trident_fuzz_metrics::FlowCallOutcome;
+use trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction;
+use trident_svm::prelude::solana_svm::transaction_processing_result::TransactionProcessingResult;
+
+use crate::trident::feedback::execution_features;
+use crate::trident::Trident;
+
+/// Score of a flow which has not been called yet
+const INITIAL_SCORE: f64 = 1.0;
+
+/// Scores the flow scores move towards after a flow call, with the rate of the move
+const NOVEL_TARGET: (f64, f64) = (4.0, 0.5);
+const PRODUCTIVE_TARGET: (f64, f64) = (2.0, 0.1);
+const FAILING_TARGET: (f64, f64) = (0.1, 0.1);
+
+/// Resolution of the learned weights, the selection works with integer weights
+const WEIGHT_SCALE: f64 = 100.0;
+
+/// Flow weights adapted to the outcomes of the flow calls, enabled by `[fuzz.flows]`
+///
+/// Every flow has a score which multiplies its weight. After each flow call the score
+/// moves towards a target given by the outcome of the call: flows reaching new execution
+/// features (including new custom error codes) are boosted, flows with successful
+/// transactions are preferred and flows whose transactions keep failing decay.
+/// Scores are kept across iterations of the thread and updated at the end of each
+/// iteration, so the weights are constant within an iteration and crash artifacts store
+/// them to reproduce the flow selection.
+#[derive(Default)]
+pub(crate) struct AdaptiveWeights {
+    enabled: bool,
+    /// Flow calls are not observed, the weights stay as they are
+    frozen: bool,
+    scores: HashMap<String, f64>,
+    base_weights: HashMap<String, u32>,
+    /// Weights of the debugged iteration, loaded from its crash artifact
+    forced: Option<BTreeMap<String, u32>>,
+    current: Option<FlowCall>,
+    /// Outcomes of the flow calls of the current iteration
+    outcomes: Vec<(String, FlowCallOutcome)>,
+    seen_features: HashSet<u64>,
+}
+
+/// Transactions of the flow call in progress
+struct FlowCall {
+    flow: String,
+    transactions: u32,
+    successful: bool,
+    novel: bool,
+}
+
+impl Trident {
+    pub(crate) fn enable_adaptive_weights(&mut self) {
+        self.adaptive.enabled = true;
+    }
+
+    pub(crate) fn freeze_adaptive_weights(&mut self, frozen: bool) {
+        self.adaptive.frozen = frozen;
+    }
+
+    /// Selects the flows with the weights of a crash artifact, without learning
+    pub(crate) fn force_adaptive_weights(&mut self, weights: BTreeMap<String, u32>) {
+        self.adaptive.enabled = true;
+        self.adaptive.frozen = true;
+        self.adaptive.forced = Some(weights);
+    }
+
+    /// Weight the flow is selected with, the base weight unless adaptive weights are enabled
+    pub(crate) fn adaptive_weight(&mut self, flow: &str, base_weight: u32) -> u32 {
+        if !self.adaptive.enabled || base_weight == 0 {
+            return base_weight;
+        }
+        if let Some(weight) = self
+            .adaptive
+            .forced
+            .as_ref()
+            .and_then(|forced| forced.get(flow))
+        {
+            return *weight;
+        }
+        self.adaptive
+            .base_weights
+            .insert(flow.to_string(), base_weight);
+        self.adaptive.learned_weight(flow, base_weight)
+    }
+
+    /// Weights of the flows selected so far, `None` unless adaptive weights are enabled
+    pub(crate) fn adaptive_weights(&self) -> Option<BTreeMap<String, u32>> {
+        if !self.adaptive.enabled {
+            return None;
+        }
+        if let Some(forced) = &self.adaptive.forced {
+            return Some(forced.clone());
+        }
+        Some(
+            self.adaptive
+                .base_weights
+                .iter()
+                .map(|(flow, base_weight)| {
+                    (
+                        flow.clone(),
+                        self.adaptive.learned_weight(flow, *base_weight),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Starts observing the transactions of a selected flow
+    pub(crate) fn begin_adaptive_flow_call(&mut self, flow: &str) {
+        if self.adaptive.frozen {
+            return;
+        }
+        self.finish_adaptive_flow_call();
+        if self.adaptive.base_weights.contains_key(flow) {
+            self.adaptive.current = Some(FlowCall {
+                flow: flow.to_string(),
+                transactions: 0,
+                successful: false,
+                novel: false,
+            });
+        }
+    }
+
+    /// Records a transaction of the flow call in progress
+    pub(crate) fn record_adaptive_feedback(
+        &mut self,
+        log_as: Option<&str>,
+        tx_result: &TransactionProcessingResult,
+    ) {
+        if self.adaptive.frozen {
+            return;
+        }
+        let Some(call) = self.adaptive.current.as_mut() else {
+            return;
+        };
+
+        call.transactions += 1;
+        if matches!(
+            tx_result,
+            Ok(ProcessedTransaction::Executed(executed_transaction))
+                if executed_transaction.execution_details.status.is_ok()
+        ) {
+            call.successful = true;
+        }
+        for feature in execution_features(log_as, tx_result) {
+            if self.adaptive.seen_features.insert(feature) {
+                call.novel = true;
+            }
+        }
+    }
+
+    /// Remembers the outcome of the finished flow call, see `learn_adaptive_weights`
+    pub(crate) fn finish_adaptive_flow_call(&mut self) {
+        let Some(call) = self.adaptive.current.take() else {
+            return;
+        };
+        // Flow calls without transactions tell nothing about the flow
+        let outcome = if call.transactions == 0 {
+            FlowCallOutcome::Idle
+        } else if call.novel {
+            FlowCallOutcome::Novel
+        } else if call.successful {
+            FlowCallOutcome::Productive
+        } else {
+            FlowCallOutcome::Failing
+        };
+        self.adaptive.outcomes.push((call.flow, outcome));
+    }
+
+    /// Updates the flow scores by the outcomes of the flow calls of the finished iteration
+    pub(crate) fn learn_adaptive_weights(&mut self) {
+        for (flow, outcome) in std::mem::take(&mut self.adaptive.outcomes) {
+            let score = self
+                .adaptive
+                .scores
+                .entry(flow.clone())
+                .or_insert(INITIAL_SCORE);
+            let (target, rate) = match outcome {
+                FlowCallOutcome::Novel => NOVEL_TARGET,
+                FlowCallOutcome::Productive => PRODUCTIVE_TARGET,
+                FlowCallOutcome::Failing => FAILING_TARGET,
+                FlowCallOutcome::Idle => (*score, 0.0),
+            };
+            *score += (target - *score) * rate;
+
+            let base_weight = self.adaptive.base_weights[&flow];
+            let learned_weight = base_weight as f64 * *score;
+            self.fuzzing_data
+                .add_flow_call(&flow, base_weight, learned_weight, outcome);
+        }
+    }
+}
+
+impl AdaptiveWeights {
+    fn learned_weight(&self, flow: &str, base_weight: u32) -> u32 {
+        let score = self.scores.get(flow).copied().unwrap_or(INITIAL_SCORE);
+        (base_weight as f64 * score * WEIGHT_SCALE).round().max(1.0) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adaptive_trident(flows: &[&str]) -> Trident {
+        let mut trident = Trident::for_tests();
+        trident.enable_adaptive_weights();
+        for flow in flows {
+            assert_eq!(trident.adaptive_weight(flow, 1), 100);
+        }
+        trident
+    }
+
+    /// Finishes a flow call with the given number of transactions
+    fn flow_call(
+        trident: &mut Trident,
+        flow: &str,
+        transactions: u32,
+        successful: bool,
+        novel: bool,
+    ) {
+        trident.begin_adaptive_flow_call(flow);
+        let call = trident.adaptive.current.as_mut().unwrap();
+        call.transactions = transactions;
+        call.successful = successful;
+        call.novel = novel;
+        trident.finish_adaptive_flow_call();
+    }
+
+    #[test]
+    fn test_without_adaptive_weights() {
+        let mut trident = Trident::for_tests();
+        assert_eq!(trident.adaptive_weight("deposit", 3), 3);
+        assert_eq!(trident.adaptive_weights(), None);
+    }
+
+    #[test]
+    fn test_outcomes_of_flow_calls() {
+        let mut trident = adaptive_trident(&["deposit"]);
+        flow_call(&mut trident, "deposit", 0, false, false);
+        flow_call(&mut trident, "deposit", 2, true, true);
+        flow_call(&mut trident, "deposit", 2, true, false);
+        flow_call(&mut trident, "deposit", 1, false, false);
+
+        let outcomes: Vec<_> = trident
+            .adaptive
+            .outcomes
+            .iter()
+            .map(|(_, outcome)| *outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                FlowCallOutcome::Idle,
+                FlowCallOutcome::Novel,
+                FlowCallOutcome::Productive,
+                FlowCallOutcome::Failing,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_weights_are_updated_after_the_iteration() {
+        let mut trident = adaptive_trident(&["novel", "productive", "failing", "idle"]);
+        flow_call(&mut trident, "novel", 1, false, true);
+        flow_call(&mut trident, "productive", 1, true, false);
+        flow_call(&mut trident, "failing", 1, false, false);
+        flow_call(&mut trident, "idle", 0, false, false);
+
+        // Weights are constant within the iteration
+        assert_eq!(trident.adaptive_weight("novel", 1), 100);
+
+        trident.learn_adaptive_weights();
+        let weights = trident.adaptive_weights().unwrap();
+        assert_eq!(weights["novel"], 250);
+        assert_eq!(weights["productive"], 110);
+        assert_eq!(weights["failing"], 91);
+        assert_eq!(weights["idle"], 100);
+    }
+
+    #[test]
+    fn test_failing_flows_decay_towards_minimal_weight() {
+        let mut trident = adaptive_trident(&["failing"]);
+        for _ in 0..200 {
+            flow_call(&mut trident, "failing", 1, false, false);
+            trident.learn_adaptive_weights();
+        }
+        assert_eq!(trident.adaptive_weight("failing", 1), 10);
+        // A flow with weight `0` stays disabled
+        assert_eq!(trident.adaptive_weight("disabled", 0), 0);
+    }
+
+    #[test]
+    fn test_frozen_weights_ignore_flow_calls() {
+        let mut trident = adaptive_trident(&["deposit"]);
+        trident.freeze_adaptive_weights(true);
+        trident.begin_adaptive_flow_call("deposit");
+        assert!(trident.adaptive.current.is_none());
+
+        trident.learn_adaptive_weights();
+        assert_eq!(trident.adaptive_weight("deposit", 1), 100);
+    }
+
+    #[test]
+    fn test_forced_weights() {
+        let mut trident = Trident::for_tests();
+        let forced = BTreeMap::from([("deposit".to_string(), 7)]);
+        trident.force_adaptive_weights(forced.clone());
+
+        assert_eq!(trident.adaptive_weight("deposit", 1), 7);
+        assert_eq!(trident.adaptive_weights(), Some(forced));
+    }
+}
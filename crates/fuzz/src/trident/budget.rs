@@ -0,0 +1,172 @@
+use std::time::Duration;
+use std::time::Instant;
+
+/// Limit of a fuzzing session
+///
+/// Either a fixed number of iterations (as passed to `fuzz()`), or a wall-clock
+/// duration shared by all fuzzing threads, which run until the duration elapses.
+#[derive(Clone, Copy)]
+pub enum FuzzingBudget {
+    Iterations(u64),
+    Duration { start: Instant, duration: Duration },
+}
+
+impl FuzzingBudget {
+    pub(crate) fn duration(duration: Duration) -> Self {
+        Self::Duration {
+            start: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Maximal number of threads the budget can keep busy
+    pub(crate) fn max_threads(&self) -> usize {
+        match self {
+            Self::Iterations(iterations) => *iterations as usize,
+            Self::Duration { .. } => usize::MAX,
+        }
+    }
+
+    /// Budget of a single thread out of `num_threads`
+    pub(crate) fn per_thread(&self, num_threads: usize) -> Self {
+        match self {
+            Self::Iterations(iterations) => Self::Iterations(iterations / num_threads as u64),
+            Self::Duration { .. } => *self,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        matches!(self, Self::Iterations(0))
+    }
+
+    pub(crate) fn is_exhausted(&self, completed_iterations: u64) -> bool {
+        match self {
+            Self::Iterations(iterations) => completed_iterations >= *iterations,
+            Self::Duration { start, duration } => start.elapsed() >= *duration,
+        }
+    }
+
+    /// Creates a progress bar measuring flow calls, or seconds in case of a duration
+    pub(crate) fn progress_bar(
+        &self,
+        prefix: &str,
+        flow_calls_per_iteration: u64,
+    ) -> indicatif::ProgressBar {
+        let (length, unit) = match self {
+            Self::Iterations(iterations) => (iterations * flow_calls_per_iteration, ""),
+            Self::Duration { duration, .. } => (duration.as_secs(), "s"),
+        };
+        let progress_bar = indicatif::ProgressBar::new(length);
+        progress_bar.set_style(
+            indicatif::ProgressStyle::with_template(&format!(
+                "{}{{spinner:.green}} [{{elapsed_precise}}] [{{wide_bar:.cyan/blue}}] {{pos}}/{{len}}{} ({{percent}}%) [{{eta_precise}}] {{msg}}",
+                prefix, unit
+            ))
+            .unwrap()
+            .progress_chars("#>-"),
+        );
+        progress_bar
+    }
+
+    /// Advances the progress bar by the executed flow calls, or to the elapsed time
+    pub(crate) fn report_progress(&self, progress_bar: &indicatif::ProgressBar, flow_calls: u64) {
+        match self {
+            Self::Iterations(_) => progress_bar.inc(flow_calls),
+            Self::Duration { start, duration } => {
+                progress_bar.set_position(start.elapsed().min(*duration).as_secs())
+            }
+        }
+    }
+
+    /// Describes the session, e.g. `100 iterations` or `7200s`
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            Self::Iterations(iterations) => format!("{} iterations", iterations),
+            Self::Duration { duration, .. } => format!("{}s", duration.as_secs()),
+        }
+    }
+
+    /// Prints the number of iterations achieved in a time-budgeted session
+    pub(crate) fn print_summary(&self, completed_iterations: u64) {
+        if let Self::Duration { start, .. } = self {
+            let elapsed = start.elapsed();
+            println!(
+                "Fuzzing finished after {}s with {} iterations ({:.1} iterations/s)",
+                elapsed.as_secs(),
+                completed_iterations,
+                completed_iterations as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elapsed_duration(elapsed: Duration, duration: Duration) -> FuzzingBudget {
+        FuzzingBudget::Duration {
+            start: Instant::now() - elapsed,
+            duration,
+        }
+    }
+
+    #[test]
+    fn test_iterations_budget() {
+        let budget = FuzzingBudget::Iterations(10);
+        assert_eq!(budget.max_threads(), 10);
+        assert!(!budget.is_empty());
+        assert!(!budget.is_exhausted(9));
+        assert!(budget.is_exhausted(10));
+        assert_eq!(budget.describe(), "10 iterations");
+
+        assert!(matches!(budget.per_thread(4), FuzzingBudget::Iterations(2)));
+        assert!(budget.per_thread(11).is_empty());
+        assert!(FuzzingBudget::Iterations(0).is_exhausted(0));
+    }
+
+    #[test]
+    fn test_duration_budget() {
+        let budget = elapsed_duration(Duration::from_secs(5), Duration::from_secs(10));
+        assert_eq!(budget.max_threads(), usize::MAX);
+        assert!(!budget.is_empty());
+        assert!(!budget.is_exhausted(u64::MAX));
+        assert_eq!(budget.describe(), "10s");
+
+        // Threads share the start and the duration of the session
+        let FuzzingBudget::Duration { start, .. } = budget else {
+            unreachable!()
+        };
+        assert!(matches!(
+            budget.per_thread(4),
+            FuzzingBudget::Duration { start: thread_start, duration }
+                if thread_start == start && duration == Duration::from_secs(10)
+        ));
+
+        let elapsed = elapsed_duration(Duration::from_secs(10), Duration::from_secs(10));
+        assert!(elapsed.is_exhausted(0));
+        assert!(FuzzingBudget::duration(Duration::ZERO).is_exhausted(0));
+    }
+
+    #[test]
+    fn test_progress_of_duration_budget() {
+        let budget = elapsed_duration(Duration::from_secs(30), Duration::from_secs(20));
+        let progress_bar = budget.progress_bar("", 5);
+        assert_eq!(progress_bar.length(), Some(20));
+
+        // The progress never exceeds the duration
+        budget.report_progress(&progress_bar, 5);
+        assert_eq!(progress_bar.position(), 20);
+    }
+
+    #[test]
+    fn test_progress_of_iterations_budget() {
+        let budget = FuzzingBudget::Iterations(4);
+        let progress_bar = budget.progress_bar("", 5);
+        assert_eq!(progress_bar.length(), Some(20));
+
+        budget.report_progress(&progress_bar, 5);
+        budget.report_progress(&progress_bar, 5);
+        assert_eq!(progress_bar.position(), 10);
+    }
+}
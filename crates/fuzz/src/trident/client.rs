@@ -2,6 +2,7 @@ use borsh::BorshDeserialize;
 use solana_sdk::instruction::Instruction;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::transaction::TransactionError;
+use trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction;
 use trident_svm::prelude::TridentTransactionProcessingResult;
 use trident_svm::processor::InstructionError;
 
@@ -27,6 +28,20 @@ impl Trident {
     /// the result including success/failure status and transaction logs. It also handles
     /// fuzzing metrics collection when enabled via environment variables.
     ///
+    /// If a compute unit budget is configured in `[fuzz.compute_units]`, a transaction which
+    /// consumes more compute units than its budget is recorded as a finding of the transaction.
+    ///
+    /// If `[fuzz.account_substitution]` is enabled, a transaction which also succeeds with
+    /// one of its accounts substituted is recorded as a finding of the transaction.
+    ///
+    /// If `lamport_conservation` is enabled in `[fuzz.invariants]`, a transaction which
+    /// changes the sum of lamports of its accounts by other than its fee is recorded as a
+    /// finding of the transaction.
+    ///
+    /// If `account_state` is enabled in `[fuzz.invariants]`, every writable account left
+    /// below the rent-exempt minimum, closed with leftover data or with an unexpectedly
+    /// changed owner is recorded as a finding of the transaction.
+    ///
     /// # Arguments
     /// * `instructions` - A slice of instructions to execute in the transaction
     /// * `transaction_name` - A descriptive name for the transaction (used in metrics)
@@ -40,11 +55,14 @@ impl Trident {
     /// let result = trident.process_transaction(&instructions, Some("Transfer SOL"));
     /// assert!(result.is_success());
     /// ```
+    #[track_caller]
     pub fn process_transaction(
         &mut self,
         instructions: &[Instruction],
         log_as: Option<&str>,
     ) -> TransactionResult {
+        self.check_iteration_timeout();
+
         let fuzzing_metrics = std::env::var("FUZZING_METRICS");
         let fuzzing_debug = std::env::var("TRIDENT_FUZZ_DEBUG");
 
@@ -60,9 +78,19 @@ impl Trident {
                 trident_svm::prelude::Level::Debug,
             );
         }
+        let substitution = self.substitute_account(instructions);
+        let lamports_before = self.lamports_before_transaction(instructions);
+        let states_before = self.account_states_before_transaction(instructions);
         let processing_data = self.process_instructions(instructions);
 
-        self.handle_tx_result(&processing_data, log_as, instructions)
+        let result = self.handle_tx_result(&processing_data, log_as, instructions);
+        self.record_transaction_trace(log_as, &result);
+        self.check_differential(instructions, log_as, &result);
+        self.check_compute_unit_budget(log_as, &result);
+        self.check_account_substitution(log_as, &result, substitution);
+        self.check_lamport_conservation(log_as, lamports_before, &processing_data);
+        self.check_account_states(log_as, states_before, &processing_data);
+        result
     }
 
     /// Deploys an entrypoint program to the SVM runtime
@@ -75,6 +103,7 @@ impl Trident {
     #[cfg(feature = "syscall-v2")]
     pub fn deploy_entrypoint(&mut self, _program: TridentEntrypoint) {
         self.client.deploy_entrypoint_program(&_program);
+        self.mirror_to_candidate(|candidate| candidate.deploy_entrypoint_program(&_program));
     }
 
     /// Deploys a binary program to the SVM runtime
@@ -84,8 +113,12 @@ impl Trident {
     ///
     /// # Arguments
     /// * `program` - The compiled program to deploy
+    ///
+    /// In differential fuzzing, the program is deployed to the candidate SVM as well,
+    /// except at the addresses of programs with a `candidate` build, which keep it.
     pub fn deploy_program(&mut self, program: TridentProgram) {
         self.client.deploy_binary_program(&program);
+        self.differential.deploy_program(&program);
     }
 
     /// Warps the blockchain clock to a specific epoch
@@ -99,7 +132,7 @@ impl Trident {
         let mut clock = self.get_sysvar::<Clock>();
 
         clock.epoch = warp_epoch;
-        self.client.set_sysvar(&clock);
+        self.set_clock(&clock);
     }
 
     /// Warps the blockchain clock to a specific slot
@@ -113,7 +146,7 @@ impl Trident {
         let mut clock = self.get_sysvar::<Clock>();
 
         clock.slot = warp_slot;
-        self.client.set_sysvar(&clock);
+        self.set_clock(&clock);
     }
     /// Warps the blockchain clock to a specific Unix timestamp
     ///
@@ -126,7 +159,7 @@ impl Trident {
         let mut clock = self.get_sysvar::<Clock>();
 
         clock.unix_timestamp = warp_timestamp;
-        self.client.set_sysvar(&clock);
+        self.set_clock(&clock);
     }
 
     /// Advances the blockchain clock by a specified number of seconds
@@ -140,7 +173,12 @@ impl Trident {
         let mut clock = self.get_sysvar::<Clock>();
 
         clock.unix_timestamp = clock.unix_timestamp.saturating_add(seconds);
-        self.client.set_sysvar(&clock);
+        self.set_clock(&clock);
+    }
+
+    fn set_clock(&mut self, clock: &Clock) {
+        self.client.set_sysvar(clock);
+        self.mirror_to_candidate(|candidate| candidate.set_sysvar(clock));
     }
 
     /// Sets a custom account state at the specified address
@@ -152,7 +190,9 @@ impl Trident {
     /// * `address` - The public key where the account should be stored
     /// * `account` - The account data to set
     pub fn set_account_custom(&mut self, address: &Pubkey, account: &AccountSharedData) {
+        self.track_snapshot_writes([*address]);
         self.client.set_account(address, account, false);
+        self.mirror_to_candidate(|candidate| candidate.set_account(address, account, false));
     }
 
     /// Returns the default payer keypair for transactions
@@ -235,6 +275,7 @@ impl Trident {
             instructions,
             Some(&self.payer().pubkey()),
         );
+        self.track_snapshot_writes(tx.message.account_keys.iter().copied());
 
         self.client.process_transaction_with_settle(tx)
     }
@@ -344,6 +385,21 @@ impl Trident {
 
         let transaction_timestamp = tx_processing_result.get_transaction_timestamp();
 
+        self.record_execution_features(log_as, tx_result);
+        self.record_adaptive_feedback(log_as, tx_result);
+
+        // Record compute units consumed by executed transactions, successful or not
+        if fuzzing_metrics.is_ok() {
+            if let (Some(log_as), Ok(ProcessedTransaction::Executed(executed_transaction))) =
+                (log_as, tx_result)
+            {
+                self.fuzzing_data.add_compute_units(
+                    log_as,
+                    executed_transaction.execution_details.executed_units,
+                );
+            }
+        }
+
         match tx_result {
             Ok(result) => match result {
                 trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction::Executed(executed_transaction) => match &executed_transaction.execution_details.status {
@@ -355,7 +411,7 @@ impl Trident {
                                     .add_successful_transaction(log_as);
                             }
                         }
-                        TransactionResult::new(Ok(()), executed_transaction.execution_details.log_messages.clone().unwrap_or_default(), transaction_timestamp)
+                        TransactionResult::new(Ok(()), executed_transaction.execution_details.log_messages.clone().unwrap_or_default(), transaction_timestamp, executed_transaction.execution_details.executed_units)
                     },
                     Err(transaction_error) => {
                         if let TransactionError::InstructionError(_error_code, instruction_error) =
@@ -372,6 +428,14 @@ impl Trident {
                                         }
                                         if log_as.is_some() {
                                             let rng = self.rng.get_seed();
+                                            let flow_calls = self.iteration_flow_calls();
+                                            self.fuzzing_data.add_transaction_panic_finding(
+                                                log_as.unwrap(),
+                                                &instruction_error.to_string(),
+                                                executed_transaction.execution_details.log_messages.as_deref(),
+                                                &rng,
+                                                flow_calls,
+                                            );
                                             // TODO format instructions
                                             let tx = format!("{:#?}", instructions);
                                             self.fuzzing_data.add_transaction_panicked(
@@ -416,12 +480,12 @@ impl Trident {
                                 );
                             }
                         }
-                        TransactionResult::new(Err(transaction_error.clone()), executed_transaction.execution_details.log_messages.clone().unwrap_or_default(), transaction_timestamp)
+                        TransactionResult::new(Err(transaction_error.clone()), executed_transaction.execution_details.log_messages.clone().unwrap_or_default(), transaction_timestamp, executed_transaction.execution_details.executed_units)
                     },
                 },
                 trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction::FeesOnly(_) => todo!(),
             },
-            Err(transaction_error) => TransactionResult::new(Err(transaction_error.clone()), vec![], transaction_timestamp),
+            Err(transaction_error) => TransactionResult::new(Err(transaction_error.clone()), vec![], transaction_timestamp, 0),
         }
     }
 }
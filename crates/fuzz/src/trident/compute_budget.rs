@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use trident_config::TridentConfig;
+
+use crate::trident::transaction_result::TransactionResult;
+use crate::trident::Trident;
+
+/// Compute unit budgets of named transactions, configured by `[fuzz.compute_units]`
+///
+/// The budget of a transaction in `transactions` takes precedence over the global `budget`.
+pub(crate) struct ComputeUnitBudgets {
+    budget: Option<u64>,
+    transactions: HashMap<String, u64>,
+}
+
+impl ComputeUnitBudgets {
+    pub(crate) fn new(config: &TridentConfig) -> Self {
+        Self {
+            budget: config.get_compute_unit_budget(),
+            transactions: config.get_transaction_compute_unit_budgets(),
+        }
+    }
+
+    fn budget_of(&self, transaction: &str) -> Option<u64> {
+        self.transactions.get(transaction).copied().or(self.budget)
+    }
+}
+
+impl Trident {
+    /// Compute unit budget invariant, records a finding of a named transaction which
+    /// consumed more compute units than its budget
+    ///
+    /// Same as the account state invariants, the finding does not stop the iteration.
+    pub(crate) fn check_compute_unit_budget(
+        &mut self,
+        log_as: Option<&str>,
+        result: &TransactionResult,
+    ) {
+        let Some(log_as) = log_as else {
+            return;
+        };
+        let Some(budget) = self.compute_units.budget_of(log_as) else {
+            return;
+        };
+        let consumed = result.get_compute_units_consumed();
+        if consumed > budget {
+            let violation = format!(
+                "consumed {} compute units, exceeding the budget of {}",
+                consumed, budget
+            );
+            if std::env::var("TRIDENT_FUZZ_DEBUG").is_ok() {
+                trident_svm::prelude::trident_svm_log::log_message(
+                    &format!("COMPUTE UNIT BUDGET VIOLATED: {}", violation),
+                    trident_svm::prelude::Level::Error,
+                );
+            }
+            let seed = self.rng.get_seed();
+            let flow_calls = self.iteration_flow_calls();
+            self.fuzzing_data
+                .add_compute_unit_budget_finding(log_as, &violation, &seed, flow_calls);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_budget_takes_precedence() {
+        let budgets = ComputeUnitBudgets {
+            budget: Some(10_000),
+            transactions: HashMap::from([("deposit".to_string(), 50_000)]),
+        };
+
+        assert_eq!(budgets.budget_of("deposit"), Some(50_000));
+        assert_eq!(budgets.budget_of("withdraw"), Some(10_000));
+    }
+
+    #[test]
+    fn test_without_budget() {
+        let budgets = ComputeUnitBudgets {
+            budget: None,
+            transactions: HashMap::from([("deposit".to_string(), 50_000)]),
+        };
+
+        assert_eq!(budgets.budget_of("withdraw"), None);
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use rand::rngs::SmallRng;
+use rand::Rng;
+use sha2::Digest;
+use sha2::Sha256;
+use trident_fuzz_metrics::write_atomically;
+
+/// Interesting values written over mutated inputs.
+///
+/// Ranges are sampled from the raw rng values, so all-zero and all-one bytes
+/// map to the bounds of the requested range.
+const INTERESTING_BYTES: [u8; 6] = [0x00, 0x01, 0x7f, 0x80, 0xfe, 0xff];
+
+/// How often a completely fresh (non-mutated) iteration is executed, 1 in N
+const FRESH_INPUT_RATIO: u32 = 8;
+
+/// Maximal number of mutations applied to a single corpus input
+const MAX_MUTATIONS: usize = 4;
+
+/// Corpus of the execution feedback mode
+///
+/// Every entry is the recorded RNG input of an iteration which reached execution
+/// features not seen before (new transaction results, custom error codes, logs, compute
+/// unit buckets or panics). Features are hashes of the processed transactions, not coverage
+/// of the programs.
+/// New iterations are created by mutating the stored inputs, the rest of an iteration
+/// (once the input is exhausted) is generated from the iteration seed.
+///
+/// If the corpus is backed by a directory, interesting inputs are saved there
+/// (one file per input, named by its hash) and loaded again by the next fuzzing session.
+/// Fuzzing processes sharing the directory import the inputs saved by each other.
+#[derive(Default)]
+pub struct Corpus {
+    inputs: Vec<Vec<u8>>,
+    features: HashSet<u64>,
+    pending: VecDeque<Vec<u8>>,
+    directory: Option<PathBuf>,
+    /// Files of the directory which were already loaded or saved
+    known_files: HashSet<PathBuf>,
+    /// Interval of importing inputs of other fuzzing processes, with the last import
+    sync: Option<(Duration, Instant)>,
+}
+
+impl Corpus {
+    /// Loads the corpus from the directory, creating the directory if it does not exist
+    ///
+    /// Loaded inputs are replayed first, before any mutations take place.
+    pub fn load(directory: impl Into<PathBuf>) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+
+        let mut corpus = Self {
+            directory: Some(directory),
+            ..Default::default()
+        };
+        corpus.load_new_files()?;
+        Ok(corpus)
+    }
+
+    /// Imports inputs saved to the directory by other fuzzing processes,
+    /// at most once per `interval`
+    pub fn with_sync(mut self, interval: Duration) -> Self {
+        self.sync = Some((interval, Instant::now()));
+        self
+    }
+
+    /// Queues the files of the directory which were not loaded or saved yet for replay
+    fn load_new_files(&mut self) -> io::Result<()> {
+        let Some(directory) = &self.directory else {
+            return Ok(());
+        };
+
+        let mut paths = fs::read_dir(directory)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && !self.known_files.contains(path))
+            // Files being written by other fuzzing processes are hidden until renamed
+            .filter(|path| {
+                !path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        for path in paths {
+            self.pending.push_back(fs::read(&path)?);
+            self.known_files.insert(path);
+        }
+        Ok(())
+    }
+
+    fn sync_if_due(&mut self) {
+        let Some((interval, last_sync)) = self.sync else {
+            return;
+        };
+        if last_sync.elapsed() < interval {
+            return;
+        }
+        self.sync = Some((interval, Instant::now()));
+        if let Err(e) = self.load_new_files() {
+            eprintln!("Warning: Failed to import corpus inputs: {}", e);
+        }
+    }
+
+    /// Number of loaded inputs which were not replayed yet
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of inputs stored in the corpus
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Number of unique execution features observed so far
+    pub fn features(&self) -> usize {
+        self.features.len()
+    }
+
+    /// Picks the input for the next iteration
+    ///
+    /// # Returns
+    /// A loaded input to replay, a mutated corpus input, or `None` if the iteration
+    /// should be generated from the seed only
+    pub(crate) fn next_input(&mut self, rng: &mut SmallRng) -> Option<Vec<u8>> {
+        self.sync_if_due();
+        if let Some(input) = self.pending.pop_front() {
+            return Some(input);
+        }
+
+        if self.inputs.is_empty() || rng.gen_ratio(1, FRESH_INPUT_RATIO) {
+            return None;
+        }
+
+        let mut input = self.inputs[rng.gen_range(0..self.inputs.len())].clone();
+        for _ in 0..rng.gen_range(1..=MAX_MUTATIONS) {
+            let donor = &self.inputs[rng.gen_range(0..self.inputs.len())];
+            mutate(&mut input, donor, rng);
+        }
+        Some(input)
+    }
+
+    /// Stores the input if it reached any execution feature not seen before
+    ///
+    /// # Returns
+    /// `true` if the input was added to the corpus
+    pub(crate) fn evaluate(&mut self, input: Vec<u8>, features: HashSet<u64>) -> bool {
+        let previous = self.features.len();
+        self.features.extend(features);
+
+        if self.features.len() > previous {
+            if let Err(e) = self.save(&input) {
+                eprintln!("Warning: Failed to save corpus input: {}", e);
+            }
+            self.inputs.push(input);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn save(&mut self, input: &[u8]) -> io::Result<()> {
+        let Some(directory) = &self.directory else {
+            return Ok(());
+        };
+
+        let path = directory.join(hex::encode(Sha256::digest(input)));
+        if !path.exists() {
+            write_atomically(&path, input)?;
+        }
+        self.known_files.insert(path);
+        Ok(())
+    }
+}
+
+fn mutate(input: &mut Vec<u8>, donor: &[u8], rng: &mut SmallRng) {
+    if input.is_empty() {
+        return;
+    }
+
+    let position = rng.gen_range(0..input.len());
+    match rng.gen_range(0..6) {
+        // Flip a single bit
+        0 => input[position] ^= 1 << rng.gen_range(0..8),
+        // Replace a byte with an interesting value
+        1 => input[position] = INTERESTING_BYTES[rng.gen_range(0..INTERESTING_BYTES.len())],
+        // Replace an aligned 8-byte value with all zeros or all ones
+        2 => {
+            let start = position - position % 8;
+            let end = (start + 8).min(input.len());
+            let value = if rng.gen_bool(0.5) { 0x00 } else { 0xff };
+            input[start..end].fill(value);
+        }
+        // Randomize a chunk
+        3 => {
+            let end = (position + rng.gen_range(1..=32)).min(input.len());
+            rng.fill(&mut input[position..end]);
+        }
+        // Truncate, the rest of the iteration is generated from the seed
+        4 => input.truncate(position),
+        // Splice with another corpus input
+        _ => {
+            if donor.len() > position {
+                input.truncate(position);
+                input.extend_from_slice(&donor[position..]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn temporary_directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("trident_corpus_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[test]
+    fn test_mutate_stays_within_input_and_donor() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let donor = vec![0xaa; 48];
+        for _ in 0..1000 {
+            let mut input = vec![0x55; 32];
+            mutate(&mut input, &donor, &mut rng);
+            assert!(input.len() <= donor.len());
+        }
+
+        let mut empty = Vec::new();
+        mutate(&mut empty, &donor, &mut rng);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_keeps_inputs_with_new_features() {
+        let mut corpus = Corpus::default();
+        assert!(corpus.evaluate(vec![1], HashSet::from([1, 2])));
+        assert!(!corpus.evaluate(vec![2], HashSet::from([2])));
+        assert!(!corpus.evaluate(vec![3], HashSet::new()));
+        assert!(corpus.evaluate(vec![4], HashSet::from([2, 3])));
+
+        assert_eq!(corpus.len(), 2);
+        assert_eq!(corpus.features(), 3);
+    }
+
+    #[test]
+    fn test_next_input_mutates_corpus_inputs() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut corpus = Corpus::default();
+        assert_eq!(corpus.next_input(&mut rng), None);
+
+        corpus.evaluate(vec![0x42; 64], HashSet::from([1]));
+        let inputs: Vec<Option<Vec<u8>>> = (0..200).map(|_| corpus.next_input(&mut rng)).collect();
+
+        // Fresh iterations are interleaved with mutated inputs
+        assert!(inputs.iter().any(Option::is_none));
+        assert!(inputs
+            .iter()
+            .flatten()
+            .any(|input| input != &vec![0x42; 64]));
+    }
+
+    #[test]
+    fn test_saved_inputs_are_replayed_first() {
+        let directory = temporary_directory("replay");
+        let mut corpus = Corpus::load(&directory).unwrap();
+        corpus.evaluate(vec![1, 2, 3], HashSet::from([1]));
+        corpus.evaluate(vec![4, 5, 6], HashSet::from([2]));
+        // Files being written by other processes are skipped
+        fs::write(directory.join(".partial.tmp"), [7]).unwrap();
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut loaded = Corpus::load(&directory).unwrap();
+        assert_eq!(loaded.pending(), 2);
+        let mut replayed = vec![
+            loaded.next_input(&mut rng).unwrap(),
+            loaded.next_input(&mut rng).unwrap(),
+        ];
+        replayed.sort();
+        assert_eq!(replayed, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(loaded.pending(), 0);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use trident_config::utils::discover_root;
+use trident_config::TridentConfig;
+
+/// Build directory of the Anchor workspace, relative to the Trident manifest
+const ANCHOR_TARGET_DIR: &str = "../target";
+
+/// ELF section flag of executable sections
+const SHF_EXECINSTR: u64 = 0x4;
+
+/// sBPF conditional jump opcodes comparing a register with an immediate
+/// (`jeq`, `jgt`, `jge`, `jne`, `jsgt`, `jsge`, `jlt`, `jle`, `jslt`, `jsle`),
+/// in their 64-bit and 32-bit variants
+const COMPARISON_OPCODES: [u8; 20] = [
+    0x15, 0x25, 0x35, 0x55, 0x65, 0x75, 0xa5, 0xb5, 0xc5, 0xd5, 0x16, 0x26, 0x36, 0x56, 0x66, 0x76,
+    0xa6, 0xb6, 0xc6, 0xd6,
+];
+
+/// Type suffixes of integer literals
+const INTEGER_TYPES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Extracts dictionary values of the fuzzed programs
+///
+/// Collects comparison immediates from the programs of `[[fuzz.programs]]` and from
+/// the `.so` files in `target/deploy`, and numeric constants from the IDLs in
+/// `target/idl` of the Anchor workspace. Every comparison immediate is added together
+/// with its neighbours, so both sides of the comparison are reachable.
+pub(crate) fn extract_dictionary(config: &TridentConfig) -> BTreeSet<i128> {
+    let mut dictionary = BTreeSet::new();
+
+    for program in config.programs() {
+        dictionary.extend(comparison_immediates(&program.data));
+    }
+
+    let Ok(root) = discover_root() else {
+        return dictionary;
+    };
+    let target = root.join(ANCHOR_TARGET_DIR);
+
+    for path in files_with_extension(&target.join("deploy"), "so") {
+        if let Ok(data) = fs::read(&path) {
+            dictionary.extend(comparison_immediates(&data));
+        }
+    }
+
+    for path in files_with_extension(&target.join("idl"), "json") {
+        if let Ok(content) = fs::read_to_string(&path) {
+            dictionary.extend(idl_constants(&content));
+        }
+    }
+
+    dictionary
+}
+
+/// Parses a dictionary value, either a decimal or a `0x` prefixed hexadecimal number,
+/// optionally negative and with `_` separators or a type suffix (e.g. `1_000u64`)
+pub(crate) fn parse_dictionary_value(value: &str) -> Option<i128> {
+    let value = value.trim().replace('_', "");
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value.to_string()),
+        None => (false, value),
+    };
+
+    let parsed = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u128::from_str_radix(hex, 16).ok(),
+        None => {
+            let (digits, suffix) = value.split_at(
+                value
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(value.len()),
+            );
+            if suffix.is_empty() || INTEGER_TYPES.contains(&suffix) {
+                digits.parse::<u128>().ok()
+            } else {
+                None
+            }
+        }
+    };
+
+    let value = i128::try_from(parsed?).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+fn files_with_extension(directory: &Path, extension: &str) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .collect()
+}
+
+/// Immediates of comparison instructions in executable sections of an sBPF ELF
+fn comparison_immediates(elf: &[u8]) -> BTreeSet<i128> {
+    let mut immediates = BTreeSet::new();
+
+    for text in executable_sections(elf) {
+        for instruction in text.chunks_exact(8) {
+            if COMPARISON_OPCODES.contains(&instruction[0]) {
+                let immediate = i32::from_le_bytes(instruction[4..8].try_into().unwrap()) as i128;
+                immediates.extend([immediate - 1, immediate, immediate + 1]);
+            }
+        }
+    }
+
+    immediates
+}
+
+/// Contents of the executable sections of a 64-bit little-endian ELF
+fn executable_sections(elf: &[u8]) -> Vec<&[u8]> {
+    let read_u16 = |offset: usize| {
+        elf.get(offset..offset + 2)
+            .map(|bytes| u16::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    };
+    let read_u64 = |offset: usize| {
+        elf.get(offset..offset + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+    };
+
+    // ELF magic, 64-bit class and little-endian data encoding
+    if elf.get(0..6) != Some(&[0x7f, b'E', b'L', b'F', 2, 1]) {
+        return Vec::new();
+    }
+    let (Some(section_headers), Some(header_size), Some(sections)) =
+        (read_u64(0x28), read_u16(0x3a), read_u16(0x3c))
+    else {
+        return Vec::new();
+    };
+
+    (0..sections)
+        .filter_map(|index| {
+            let header = section_headers as usize + index * header_size;
+            let flags = read_u64(header + 8)?;
+            let offset = read_u64(header + 24)? as usize;
+            let size = read_u64(header + 32)? as usize;
+            if flags & SHF_EXECINSTR != 0 {
+                elf.get(offset..offset.checked_add(size)?)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Numeric constants of an Anchor IDL
+fn idl_constants(idl: &str) -> BTreeSet<i128> {
+    let Ok(idl) = serde_json::from_str::<serde_json::Value>(idl) else {
+        return BTreeSet::new();
+    };
+    idl.get("constants")
+        .and_then(|constants| constants.as_array())
+        .map(|constants| {
+            constants
+                .iter()
+                .filter_map(|constant| constant.get("value")?.as_str())
+                .filter_map(parse_dictionary_value)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 64-bit little-endian ELF with a data section followed by an executable section
+    fn elf_with_text(text: &[u8]) -> Vec<u8> {
+        let data = [0x15, 0x01, 0x00, 0x00, 0x63, 0x00, 0x00, 0x00];
+        let data_offset = 64;
+        let text_offset = data_offset + data.len();
+        let section_headers = text_offset + text.len();
+
+        let mut elf = vec![0; 64];
+        elf[0..6].copy_from_slice(&[0x7f, b'E', b'L', b'F', 2, 1]);
+        elf[0x28..0x30].copy_from_slice(&(section_headers as u64).to_le_bytes());
+        elf[0x3a..0x3c].copy_from_slice(&64u16.to_le_bytes());
+        elf[0x3c..0x3e].copy_from_slice(&2u16.to_le_bytes());
+        elf.extend_from_slice(&data);
+        elf.extend_from_slice(text);
+
+        for (flags, offset, size) in [
+            (0, data_offset, data.len()),
+            (SHF_EXECINSTR, text_offset, text.len()),
+        ] {
+            let mut header = [0; 64];
+            header[8..16].copy_from_slice(&flags.to_le_bytes());
+            header[24..32].copy_from_slice(&(offset as u64).to_le_bytes());
+            header[32..40].copy_from_slice(&(size as u64).to_le_bytes());
+            elf.extend_from_slice(&header);
+        }
+        elf
+    }
+
+    fn instruction(opcode: u8, immediate: i32) -> [u8; 8] {
+        let mut instruction = [opcode, 0x01, 0x00, 0x00, 0, 0, 0, 0];
+        instruction[4..8].copy_from_slice(&immediate.to_le_bytes());
+        instruction
+    }
+
+    #[test]
+    fn test_parse_dictionary_value() {
+        assert_eq!(parse_dictionary_value("42"), Some(42));
+        assert_eq!(parse_dictionary_value(" -7 "), Some(-7));
+        assert_eq!(parse_dictionary_value("1_000u64"), Some(1000));
+        assert_eq!(parse_dictionary_value("0xff"), Some(255));
+        assert_eq!(parse_dictionary_value("-0X10"), Some(-16));
+        assert_eq!(
+            parse_dictionary_value("340282366920938463463374607431768211455"),
+            None
+        );
+        assert_eq!(parse_dictionary_value("12abc"), None);
+        assert_eq!(parse_dictionary_value("0xfg"), None);
+        assert_eq!(parse_dictionary_value("1.5"), None);
+        assert_eq!(parse_dictionary_value(""), None);
+    }
+
+    #[test]
+    fn test_comparison_immediates() {
+        let text = [
+            // jeq r1, 42
+            instruction(0x15, 42),
+            // mov64 r1, 7 is not a comparison
+            instruction(0xb7, 7),
+            // jslt32 r1, -5
+            instruction(0xc6, -5),
+        ]
+        .concat();
+        let elf = elf_with_text(&text);
+
+        assert_eq!(executable_sections(&elf), vec![&text[..]]);
+        assert_eq!(
+            comparison_immediates(&elf),
+            BTreeSet::from([-6, -5, -4, 41, 42, 43])
+        );
+    }
+
+    #[test]
+    fn test_malformed_elf() {
+        let elf = elf_with_text(&instruction(0x15, 42));
+
+        assert!(executable_sections(&elf[..elf.len() - 32]).is_empty());
+        assert!(executable_sections(&elf[..32]).is_empty());
+        assert!(executable_sections(b"not an elf").is_empty());
+        assert!(comparison_immediates(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_idl_constants() {
+        let idl = r#"{
+            "constants": [
+                { "name": "MAX_DEPOSIT", "type": "u64", "value": "1_000_000" },
+                { "name": "SEED", "type": "bytes", "value": "[115, 101, 101, 100]" },
+                { "name": "MIN_PRICE", "type": "i64", "value": "-5" }
+            ]
+        }"#;
+        assert_eq!(idl_constants(idl), BTreeSet::from([-5, 1_000_000]));
+        assert!(idl_constants("{}").is_empty());
+        assert!(idl_constants("not json").is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::account::ReadableAccount;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use trident_config::fuzz::FuzzProgram;
+use trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction;
+use trident_svm::trident_svm::TridentSVM;
+use trident_svm::types::trident_program::TridentProgram;
+
+use crate::trident::transaction_result::TransactionResult;
+use crate::trident::Trident;
+
+/// Differential fuzzing, enabled by a `candidate` build of a program in `[[fuzz.programs]]`
+///
+/// Every transaction is executed by a second SVM as well, in which the candidate builds
+/// replace the programs. Accounts, the clock and deployed programs are mirrored to the
+/// candidate SVM, so both SVMs execute the same flow sequence from the same state.
+/// Programs deployed by the fuzz test never replace the candidate builds.
+/// The iteration fails at the first transaction whose result, or any of whose accounts,
+/// differs between the baseline and the candidate.
+#[derive(Default)]
+pub(crate) struct Differential {
+    candidate: Option<TridentSVM>,
+    /// Programs of `[[fuzz.programs]]` with a `candidate` build
+    candidate_programs: Vec<FuzzProgram>,
+}
+
+impl Differential {
+    /// Brings the candidate SVM to the state of the baseline SVM
+    pub(crate) fn new(
+        baseline: &TridentSVM,
+        candidate: Option<TridentSVM>,
+        programs: &[FuzzProgram],
+    ) -> Self {
+        let Some(mut candidate) = candidate else {
+            return Self::default();
+        };
+        // Transactions of both SVMs are paid by the payer of the baseline
+        let payer = baseline.get_payer().pubkey();
+        if let Some(account) = baseline.get_account(&payer) {
+            candidate.set_account(&payer, &account, true);
+        }
+        candidate.set_sysvar(&baseline.get_sysvar::<Clock>());
+        Self {
+            candidate: Some(candidate),
+            candidate_programs: programs
+                .iter()
+                .filter(|program| program.candidate.is_some())
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Sets the account in the candidate SVM, if differential fuzzing is enabled
+    pub(crate) fn set_account(&mut self, address: &Pubkey, account: &AccountSharedData) {
+        if let Some(candidate) = self.candidate.as_mut() {
+            candidate.set_account(address, account, false);
+        }
+    }
+
+    /// Deploys the program to the candidate SVM, if differential fuzzing is enabled
+    ///
+    /// The program id of a `TridentProgram` cannot be read, so the candidate builds are
+    /// deployed again afterwards in case the program was deployed at one of their addresses.
+    pub(crate) fn deploy_program(&mut self, program: &TridentProgram) {
+        let Some(candidate) = self.candidate.as_mut() else {
+            return;
+        };
+        candidate.deploy_binary_program(program);
+        for candidate_program in &self.candidate_programs {
+            let Some(candidate_data) = &candidate_program.candidate else {
+                continue;
+            };
+            candidate.deploy_binary_program(&TridentProgram::new(
+                candidate_program.address,
+                candidate_program.upgrade_authority,
+                candidate_data.clone(),
+            ));
+        }
+    }
+}
+
+impl Trident {
+    /// Applies a change of the baseline SVM to the candidate SVM, if differential fuzzing is enabled
+    pub(crate) fn mirror_to_candidate(&mut self, change: impl FnOnce(&mut TridentSVM)) {
+        if let Some(candidate) = self.differential.candidate.as_mut() {
+            change(candidate);
+        }
+    }
+
+    /// Executes the transaction by the candidate SVM and fails the iteration if its result,
+    /// or any of its accounts, differs from the baseline SVM
+    #[track_caller]
+    pub(crate) fn check_differential(
+        &mut self,
+        instructions: &[Instruction],
+        log_as: Option<&str>,
+        result: &TransactionResult,
+    ) {
+        let Some(candidate) = self.differential.candidate.as_mut() else {
+            return;
+        };
+        let tx = Transaction::new_with_payer(instructions, Some(&self.client.get_payer().pubkey()));
+        let account_keys = tx.message.account_keys.clone();
+
+        let processing_data = candidate.process_transaction_with_settle(tx);
+        // Both SVMs advance their clock by the elapsed wall-clock time, keep them in lockstep
+        candidate.set_sysvar(&self.client.get_sysvar::<Clock>());
+
+        let candidate_result = match &processing_data.get_result().processing_results[0] {
+            Ok(ProcessedTransaction::Executed(executed_transaction)) => {
+                executed_transaction.execution_details.status.clone()
+            }
+            Ok(ProcessedTransaction::FeesOnly(fees_only)) => Err(fees_only.load_error.clone()),
+            Err(transaction_error) => Err(transaction_error.clone()),
+        };
+
+        let transaction = log_as.unwrap_or("without name");
+        if result.get_result() != &candidate_result {
+            panic!(
+                "Differential divergence in transaction {}: the baseline returned {:?}, the candidate returned {:?}",
+                transaction,
+                result.get_result(),
+                candidate_result
+            );
+        }
+
+        for address in account_keys {
+            let baseline_account = self.client.get_account(&address).unwrap_or_default();
+            let candidate_account = candidate.get_account(&address).unwrap_or_default();
+            // Program accounts differ by design
+            if baseline_account.owner() == &solana_sdk::bpf_loader_upgradeable::id() {
+                continue;
+            }
+            if let Some(difference) = account_difference(&baseline_account, &candidate_account) {
+                panic!(
+                    "Differential divergence in transaction {}: account {} {}",
+                    transaction, address, difference
+                );
+            }
+        }
+    }
+}
+
+/// Describes the first difference of the account between the baseline and the candidate SVM
+fn account_difference(
+    baseline: &AccountSharedData,
+    candidate: &AccountSharedData,
+) -> Option<String> {
+    if baseline.lamports() != candidate.lamports() {
+        return Some(format!(
+            "has {} lamports in the baseline and {} lamports in the candidate",
+            baseline.lamports(),
+            candidate.lamports()
+        ));
+    }
+    if baseline.owner() != candidate.owner() {
+        return Some(format!(
+            "is owned by {} in the baseline and by {} in the candidate",
+            baseline.owner(),
+            candidate.owner()
+        ));
+    }
+    if baseline.data().len() != candidate.data().len() {
+        return Some(format!(
+            "has {} bytes of data in the baseline and {} bytes in the candidate",
+            baseline.data().len(),
+            candidate.data().len()
+        ));
+    }
+    baseline
+        .data()
+        .iter()
+        .zip(candidate.data())
+        .position(|(baseline, candidate)| baseline != candidate)
+        .map(|offset| {
+            format!(
+                "differs at data offset {} (baseline byte {:#04x}, candidate byte {:#04x})",
+                offset,
+                baseline.data()[offset],
+                candidate.data()[offset]
+            )
+        })
+}
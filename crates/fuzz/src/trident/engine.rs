@@ -0,0 +1,99 @@
+use std::any::Any;
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Read;
+use std::panic::catch_unwind;
+use std::panic::resume_unwind;
+use std::panic::AssertUnwindSafe;
+
+use crate::trident::flow_executor::FlowExecutor;
+use crate::trident::flow_executor::PANIC_LOCATION;
+use crate::trident::Trident;
+
+/// Seed of the rng used once an input is exhausted
+///
+/// The seed is the same for every input, so the iteration is fully determined by the
+/// input and the engine can reproduce it.
+const INPUT_FALLBACK_SEED: [u8; 32] = [0; 32];
+
+// Fuzz test instances reused across inputs, one per fuzz test type and thread.
+// Creating the fuzz test (and its SVM) for every input would dominate the execution time.
+thread_local! {
+    static FUZZERS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+impl Trident {
+    fn begin_input_iteration(&mut self, input: &[u8]) {
+        self.rng.set_master_seed_for_debug(INPUT_FALLBACK_SEED);
+        self.rng.set_input(input.to_vec());
+        // The fallback seed is the same for every input, the engine chooses the flows
+        self.disable_swarm();
+    }
+}
+
+/// Executes a single iteration driven by an input of an external fuzzing engine
+///
+/// Panics of the iteration are propagated to the engine after the fuzz test is
+/// prepared for the next input.
+///
+/// # Arguments
+/// * `input` - Bytes generated by the engine, consumed by every random decision
+/// * `flow_calls_per_iteration` - Number of flow calls of the iteration
+pub(crate) fn fuzz_input<F: FlowExecutor>(input: &[u8], flow_calls_per_iteration: u64) {
+    let result = FUZZERS.with(|fuzzers| {
+        let mut fuzzers = fuzzers.borrow_mut();
+        let fuzzer = fuzzers
+            .entry(TypeId::of::<F>())
+            .or_insert_with(|| Box::new(F::new()))
+            .downcast_mut::<F>()
+            .expect("Fuzz test instance of a different type");
+
+        fuzzer.trident_mut().begin_input_iteration(input);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _ = fuzzer.execute_flows(flow_calls_per_iteration);
+        }));
+
+        fuzzer.trident_mut().next_iteration();
+        fuzzer.reset_fuzz_accounts();
+        result
+    });
+
+    if let Err(panic_err) = result {
+        resume_unwind(panic_err);
+    }
+}
+
+/// Executes a single iteration driven by the content of a file, or of stdin for `-`
+///
+/// Used by engines which execute the fuzz test binary for every input (AFL++ or
+/// honggfuzz without persistent mode). A failing iteration aborts the process, so the
+/// engine records the input as a crash.
+///
+/// # Arguments
+/// * `path` - Path of the input file, or `-` to read the input from stdin
+/// * `flow_calls_per_iteration` - Number of flow calls of the iteration
+pub(crate) fn fuzz_input_file<F: FlowExecutor>(path: &str, flow_calls_per_iteration: u64) {
+    let mut input = Vec::new();
+    let read = if path == "-" {
+        std::io::stdin().read_to_end(&mut input).map(|_| ())
+    } else {
+        std::fs::read(path).map(|content| input = content)
+    };
+    if let Err(e) = read {
+        eprintln!("Failed to read fuzzing input {}: {}", path, e);
+        std::process::exit(1);
+    }
+
+    if let Err(panic_err) = catch_unwind(|| fuzz_input::<F>(&input, flow_calls_per_iteration)) {
+        let location = PANIC_LOCATION
+            .with(|cell| cell.take())
+            .unwrap_or_else(|| "unknown".to_string());
+        eprintln!(
+            "Assertion failed at {}: {}",
+            location,
+            F::extract_panic_message(&panic_err)
+        );
+        std::process::abort();
+    }
+}
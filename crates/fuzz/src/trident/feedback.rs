@@ -0,0 +1,166 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use trident_fuzz_metrics::collapse_digits;
+use trident_fuzz_metrics::is_address_like;
+use trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction;
+use trident_svm::prelude::solana_svm::transaction_processing_result::TransactionProcessingResult;
+
+use crate::trident::Trident;
+
+/// Execution features observed during the current iteration
+///
+/// Used as the feedback signal of the execution feedback mode. A feature is a hash of
+/// something observable about a processed transaction: the result, a normalized
+/// log line (program invocations, CPIs, program logs) or the status and all log lines
+/// together with a compute unit bucket. The features are not coverage of the programs,
+/// branches which do not change the result, the logs or the magnitude of the consumed
+/// compute units are not distinguished.
+#[derive(Default)]
+pub(crate) struct ExecutionFeedback {
+    enabled: bool,
+    features: HashSet<u64>,
+}
+
+impl Trident {
+    /// Prepares the next iteration in execution feedback mode
+    ///
+    /// The RNG consumes `input` before falling back to the iteration seed and records
+    /// everything it hands out, so the iteration can be stored and mutated later.
+    pub(crate) fn begin_guided_iteration(&mut self, input: Option<Vec<u8>>) {
+        self.rng.set_input_and_record(input.unwrap_or_default());
+        self.feedback.enabled = true;
+        self.feedback.features.clear();
+    }
+
+    /// Finishes the current execution feedback iteration
+    ///
+    /// # Returns
+    /// The recorded RNG input and the execution features observed during the iteration
+    pub(crate) fn finish_guided_iteration(&mut self) -> (Vec<u8>, HashSet<u64>) {
+        self.feedback.enabled = false;
+        (
+            self.rng.take_recorded_input(),
+            std::mem::take(&mut self.feedback.features),
+        )
+    }
+
+    /// Stops recording execution features
+    ///
+    /// # Returns
+    /// Whether the features were recorded, to be passed to `resume_feedback`
+    pub(crate) fn suspend_feedback(&mut self) -> bool {
+        std::mem::take(&mut self.feedback.enabled)
+    }
+
+    pub(crate) fn resume_feedback(&mut self, enabled: bool) {
+        self.feedback.enabled = enabled;
+    }
+
+    /// Records execution features of a processed transaction (execution feedback mode only)
+    pub(crate) fn record_execution_features(
+        &mut self,
+        log_as: Option<&str>,
+        tx_result: &TransactionProcessingResult,
+    ) {
+        if !self.feedback.enabled {
+            return;
+        }
+
+        self.feedback
+            .features
+            .extend(execution_features(log_as, tx_result));
+    }
+
+    /// Records a panic of the current iteration (execution feedback mode only)
+    ///
+    /// Numbers in the message are ignored, so assertions printing values
+    /// are reported as a single feature.
+    pub(crate) fn record_panic_feature(&mut self, location: &str, message: &str) {
+        if self.feedback.enabled {
+            self.feedback.features.insert(feature_hash(&(
+                "panic",
+                location,
+                collapse_digits(message),
+            )));
+        }
+    }
+}
+
+/// Execution features of a processed transaction
+pub(crate) fn execution_features(
+    log_as: Option<&str>,
+    tx_result: &TransactionProcessingResult,
+) -> Vec<u64> {
+    let mut features = Vec::new();
+
+    let (status, logs, executed_units) = match tx_result {
+        Ok(ProcessedTransaction::Executed(executed_transaction)) => {
+            let details = &executed_transaction.execution_details;
+            (
+                format!("{:?}", details.status),
+                details.log_messages.as_deref().unwrap_or_default(),
+                details.executed_units,
+            )
+        }
+        Ok(ProcessedTransaction::FeesOnly(fees_only)) => {
+            (format!("{:?}", fees_only.load_error), &[][..], 0)
+        }
+        Err(transaction_error) => (format!("{:?}", transaction_error), &[][..], 0),
+    };
+
+    features.push(feature_hash(&("status", log_as, &status)));
+
+    let normalized: Vec<String> = logs.iter().map(|line| normalize_log_line(line)).collect();
+    for line in &normalized {
+        features.push(feature_hash(&("log", line)));
+    }
+
+    // Compute units are bucketed by their magnitude so that small changes
+    // in consumption are not reported as new behavior
+    let units_bucket = u64::BITS - executed_units.leading_zeros();
+    features.push(feature_hash(&(
+        "path",
+        log_as,
+        &status,
+        &normalized,
+        units_bucket,
+    )));
+
+    features
+}
+
+fn feature_hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Removes values which differ between otherwise identical executions
+/// (amounts, compute units, account addresses, event data) from a log line.
+/// Program ids of `Program <id> ...` lines are kept.
+fn normalize_log_line(line: &str) -> String {
+    if line.starts_with("Program data:") {
+        return "Program data:".to_string();
+    }
+
+    let keep_program_id = line.starts_with("Program ")
+        && !line.starts_with("Program log:")
+        && !line.starts_with("Program return:");
+
+    line.split_whitespace()
+        .enumerate()
+        .map(|(position, token)| {
+            if position == 1 && keep_program_id {
+                token.to_string()
+            } else if is_address_like(token) {
+                "<address>".to_string()
+            } else {
+                collapse_digits(token)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
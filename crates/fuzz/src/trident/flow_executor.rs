@@ -1,21 +1,46 @@
 use std::panic::catch_unwind;
 use std::panic::AssertUnwindSafe;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
 use std::thread;
+use std::time::Duration;
 use std::time::Instant;
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use trident_fuzz_metrics::CrashArtifact;
+use trident_fuzz_metrics::FindingUpdate;
 use trident_fuzz_metrics::TridentFuzzingData;
 
+use crate::trident::budget::FuzzingBudget;
+use crate::trident::corpus::Corpus;
+use crate::trident::snapshot::take_init_snapshot;
+use crate::trident::stop_signal::StopReason;
+use crate::trident::stop_signal::StopSignal;
+use crate::trident::sync::SyncDir;
+use crate::trident::sync::CORPUS_SYNC_INTERVAL;
+use crate::trident::sync::ENV_FUZZ_MERGE;
+use crate::trident::trace::save_failure_trace;
+use crate::trident::watchdog::wait_for_fuzzer;
+use crate::trident::watchdog::FuzzerOutcome;
+use crate::trident::watchdog::IterationTimeout;
+use crate::trident::watchdog::TIMEOUT_EXIT_CODE;
 use crate::trident::Trident;
 
 // Thread-local storage for panic location information.
 // When a panic occurs, the panic handler stores the location here so we can retrieve it
 // after catching the panic with catch_unwind.
 thread_local! {
-    static PANIC_LOCATION: std::cell::Cell<Option<String>> = const { std::cell::Cell::new(None) };
+    pub(crate) static PANIC_LOCATION: std::cell::Cell<Option<String>> = const { std::cell::Cell::new(None) };
 }
 
+// Message of the first failure of the session, reported when fail-fast mode stops the fuzzing.
+static FIRST_FAILURE: OnceLock<String> = OnceLock::new();
+
 /// Configuration constants for the flow executor
 mod config {
     use std::time::Duration;
@@ -32,10 +57,42 @@ mod config {
     /// Environment variable names
     pub const ENV_FUZZ_DEBUG: &str = "TRIDENT_FUZZ_DEBUG";
     pub const ENV_FUZZ_SEED: &str = "TRIDENT_FUZZ_SEED";
+    pub const ENV_FUZZ_GUIDED: &str = "TRIDENT_FUZZ_GUIDED";
+    pub const ENV_FUZZ_CORPUS: &str = "TRIDENT_FUZZ_CORPUS";
+    pub const ENV_FUZZ_MINIMIZE: &str = "TRIDENT_FUZZ_MINIMIZE";
+    pub const ENV_FUZZ_REPLAY: &str = "TRIDENT_FUZZ_REPLAY";
+    pub const ENV_FUZZ_DURATION: &str = "TRIDENT_FUZZ_DURATION";
+    pub const ENV_FUZZ_FAIL_FAST: &str = "TRIDENT_FUZZ_FAIL_FAST";
+    pub const ENV_FUZZ_INPUT: &str = "TRIDENT_FUZZ_INPUT";
+    pub const ENV_FUZZ_ADAPTIVE: &str = "TRIDENT_FUZZ_ADAPTIVE";
+    pub const ENV_FUZZ_ITERATION_TIMEOUT: &str = "TRIDENT_FUZZ_ITERATION_TIMEOUT";
+    pub const ENV_FUZZ_EXIT_ON_TIMEOUT: &str = "TRIDENT_FUZZ_EXIT_ON_TIMEOUT";
     pub const ENV_FUZZING_METRICS: &str = "FUZZING_METRICS";
     pub const ENV_WITH_EXIT_CODE: &str = "TRIDENT_WITH_EXIT_CODE";
 }
 
+/// Outputs fuzzing metrics (JSON, dashboard, etc.) if metrics are enabled,
+/// otherwise only the unique findings.
+fn output_metrics_if_enabled(fuzzing_data: &TridentFuzzingData) {
+    if std::env::var(config::ENV_FUZZING_METRICS).is_ok() {
+        if let Err(e) = fuzzing_data.generate() {
+            eprintln!("Warning: Failed to generate metrics: {}", e);
+        }
+    } else {
+        fuzzing_data.show_findings();
+        fuzzing_data.show_flow_weights();
+    }
+}
+
+/// Reports the fuzzing data at the end of the session, workers of a distributed campaign
+/// save their data instead, it is reported once merged with the other workers
+pub(crate) fn report_fuzzing_data(fuzzing_data: &TridentFuzzingData) {
+    match SyncDir::from_env() {
+        Some(sync) => sync.save_worker_data(fuzzing_data),
+        None => output_metrics_if_enabled(fuzzing_data),
+    }
+}
+
 /// Trait for executing fuzzing flows in the Trident framework
 ///
 /// This trait defines the interface for fuzzing executors that can run
@@ -79,6 +136,13 @@ pub trait FlowExecutor: Send + 'static + Sized {
     /// single-threaded and parallel execution based on the environment
     /// and available system resources.
     ///
+    /// If `TRIDENT_FUZZ_INPUT` is set to a file path (or `-` for stdin), a single
+    /// iteration driven by the content of the file is executed instead, see `fuzz_input`.
+    ///
+    /// If a duration is configured (`TRIDENT_FUZZ_DURATION` in seconds, set by
+    /// `trident fuzz run --duration`), fuzzing runs until the duration elapses
+    /// and `iterations` is ignored.
+    ///
     /// # Arguments
     /// * `iterations` - Total number of fuzzing iterations to run
     /// * `flow_calls_per_iteration` - Number of flow calls per iteration
@@ -86,37 +150,129 @@ pub trait FlowExecutor: Send + 'static + Sized {
         // Setup panic handler to capture location information when panics occur
         Self::setup_panic_handler();
 
+        // Input mode: run a single iteration driven by an input of an external fuzzing engine
+        if let Ok(input_path) = std::env::var(config::ENV_FUZZ_INPUT) {
+            crate::trident::engine::fuzz_input_file::<Self>(&input_path, flow_calls_per_iteration);
+            return;
+        }
+
+        // Minimize mode: delta-debug the flow calls of a failing iteration
+        if let Ok(failing_iteration) = std::env::var(config::ENV_FUZZ_MINIMIZE) {
+            crate::trident::minimize::minimize::<Self>(
+                &failing_iteration,
+                flow_calls_per_iteration,
+            );
+            return;
+        }
+
+        // Replay mode: run the iteration recorded in a replay file
+        if let Ok(replay_path) = std::env::var(config::ENV_FUZZ_REPLAY) {
+            crate::trident::replay::replay::<Self>(&replay_path);
+            return;
+        }
+
+        // Merge mode: report the fuzzing data of all workers of a distributed campaign
+        if std::env::var(ENV_FUZZ_MERGE).is_ok() {
+            Self::merge_workers();
+            return;
+        }
+
         // Debug mode: run single iteration with provided seed (for reproducing specific failures)
         if std::env::var(config::ENV_FUZZ_DEBUG).is_ok() {
             println!("Debug mode detected: Running single iteration with provided seed");
-            Self::fuzz_single_threaded(1, flow_calls_per_iteration);
+            Self::fuzz_single_threaded(FuzzingBudget::Iterations(1), flow_calls_per_iteration);
             return;
         }
 
         // Get or generate master seed for reproducible fuzzing
         let master_seed = Self::get_or_generate_master_seed();
 
-        // Determine number of threads to use (limited by available parallelism and iteration count)
-        let num_threads = thread::available_parallelism()
+        // Run for the configured duration, or the given number of iterations
+        let mut budget = Self::get_budget(iterations);
+
+        let mut available_threads = thread::available_parallelism()
             .map(|n| n.get())
-            .unwrap_or(1)
-            .min(iterations as usize);
+            .unwrap_or(1);
+
+        // Distributed mode: workers started on this machine share its threads and iterations
+        let sync = SyncDir::from_env();
+        if let Some(sync) = &sync {
+            budget = budget.per_thread(sync.workers());
+            available_threads = (available_threads / sync.workers()).max(1);
+        }
+
+        // Determine number of threads to use (limited by available parallelism and iteration count)
+        let num_threads = available_threads.min(budget.max_threads());
+
+        // Execution feedback mode: threads share a corpus of iterations which reached new
+        // execution features, so it always runs through the parallel executor
+        if std::env::var(config::ENV_FUZZ_GUIDED).is_ok() {
+            println!("Execution feedback mode enabled");
+            Self::fuzz_parallel(
+                budget,
+                flow_calls_per_iteration,
+                num_threads.max(1),
+                master_seed,
+                Some(Arc::new(Mutex::new(Self::load_corpus()))),
+            );
+            return;
+        }
 
-        // Use single-threaded mode if we only have one thread or one iteration
-        if num_threads <= 1 || iterations <= 1 {
-            Self::fuzz_single_threaded(iterations, flow_calls_per_iteration);
+        // Use single-threaded mode if we only have one thread or one iteration, workers
+        // always run the parallel executor which derives the iterations from their seed
+        if sync.is_none() && (num_threads <= 1 || budget.max_threads() <= 1) {
+            Self::fuzz_single_threaded(budget, flow_calls_per_iteration);
             return;
         }
 
         // Use parallel mode for better performance with multiple threads
         Self::fuzz_parallel(
-            iterations,
+            budget,
             flow_calls_per_iteration,
-            num_threads,
+            num_threads.max(1),
             master_seed,
+            None,
         );
     }
 
+    /// Reports the merged fuzzing data of all workers sharing the sync directory
+    /// and exits with the exit code of the whole campaign
+    fn merge_workers() {
+        let sync = SyncDir::from_env().expect("Merging worker data requires a sync directory");
+        let (fuzzing_data, workers) = sync.merge_worker_data();
+        println!(
+            "Merged the fuzzing data of {} workers from {}",
+            workers,
+            sync.display()
+        );
+        output_metrics_if_enabled(&fuzzing_data);
+
+        let with_exit_code = std::env::var(config::ENV_WITH_EXIT_CODE).is_ok();
+        std::process::exit(Self::determine_exit_code(
+            with_exit_code,
+            fuzzing_data.has_findings(),
+            None,
+            &fuzzing_data,
+        ));
+    }
+
+    /// Executes a single iteration driven by an input of an external fuzzing engine
+    ///
+    /// Every random decision of the iteration (flow selection and all `random_*` values)
+    /// consumes bytes of the input, so engines such as `cargo fuzz` (libFuzzer), honggfuzz
+    /// or AFL++ can mutate the input and drive the fuzz test with their own coverage feedback.
+    /// Once the input is exhausted, values are generated from a fixed seed. The fuzz test
+    /// instance is reused across inputs of the same thread.
+    ///
+    /// Panics of the iteration are propagated, so the engine reports them as crashes.
+    ///
+    /// # Arguments
+    /// * `input` - Bytes generated by the engine
+    /// * `flow_calls_per_iteration` - Number of flow calls of the iteration
+    fn fuzz_input(input: &[u8], flow_calls_per_iteration: u64) {
+        crate::trident::engine::fuzz_input::<Self>(input, flow_calls_per_iteration);
+    }
+
     /// Sets up a global panic handler that captures panic location information.
     /// This allows us to retrieve the file, line, and column where a panic occurred
     /// even after catching it with catch_unwind.
@@ -144,39 +300,144 @@ pub trait FlowExecutor: Send + 'static + Sized {
     }
 
     /// Handles a caught panic by logging it and updating the panic tracking flag.
-    /// Returns the formatted panic message for display.
+    /// Returns the formatted panic message for display, or `None` if the panic is
+    /// a known finding (same location and message up to numbers and addresses) which
+    /// was not reproduced with fewer flow calls.
+    ///
+    /// Iterations aborted by the watchdog are reported as timeout findings, and stop
+    /// the session if `exit_on_timeout` is enabled. Failed `#[invariant]` checks are
+    /// reported with the flow after which they failed.
+    ///
+    /// New findings are executed once more with their trace recorded to write the crash
+    /// artifact, see `save_failure_trace`.
     fn handle_panic(
         panic_err: &Box<dyn std::any::Any + Send>,
         fuzzer: &mut Self,
+        flow_calls_per_iteration: u64,
         panic_occurred: Option<&Arc<AtomicBool>>,
-    ) -> String {
+    ) -> Option<String> {
         // Mark that a panic occurred (for exit code handling)
         if let Some(flag) = panic_occurred {
             flag.store(true, Ordering::Relaxed);
         }
 
+        // In fail-fast mode, the first failure stops all fuzzers of the session
+        if std::env::var(config::ENV_FUZZ_FAIL_FAST).is_ok() {
+            fuzzer
+                .trident_mut()
+                .stop_signal()
+                .stop(StopReason::FailFast);
+        }
+
         // Extract panic details
-        let message = Self::extract_panic_message(panic_err);
         let location =
             PANIC_LOCATION.with(|cell| cell.take().unwrap_or_else(|| "unknown".to_string()));
-        let seed = hex::encode(fuzzer.trident_mut().get_current_seed());
+        let timeout = panic_err.downcast_ref::<IterationTimeout>();
+        let failed_invariant = fuzzer.trident_mut().take_failed_invariant();
+        let (location, message, failure) = match (timeout, failed_invariant) {
+            (Some(timeout), _) => (
+                timeout.flow.clone().unwrap_or_else(|| "init".to_string()),
+                format!("iteration timed out after {:.1?}", timeout.elapsed),
+                "Timeout in".to_string(),
+            ),
+            (None, Some(check)) => (
+                location,
+                Self::extract_panic_message(panic_err),
+                format!(
+                    "Invariant {} violated after flow {} at",
+                    check.invariant, check.flow
+                ),
+            ),
+            (None, None) => (
+                location,
+                Self::extract_panic_message(panic_err),
+                "Assertion failed at".to_string(),
+            ),
+        };
+        let trident = fuzzer.trident_mut();
+        let seed_bytes = trident.get_current_seed();
+        let seed = hex::encode(seed_bytes);
+        let init_seed = trident.restored_init_snapshot_seed();
+        trident.record_panic_feature(&location, &message);
+
+        // Report only new findings and shorter reproducers of known ones
+        let flow_calls = trident.iteration_flow_calls();
+        let update = if timeout.is_some() {
+            trident
+                .fuzzing_data
+                .add_timeout_finding(&location, &message, &seed_bytes, flow_calls)
+        } else {
+            trident
+                .fuzzing_data
+                .add_panic_finding(&location, &message, &seed_bytes, flow_calls)
+        };
+        if update == FindingUpdate::Known {
+            return None;
+        }
+        trident.publish_findings_to_watchdog();
+
+        // Save the reproduction data if crash artifacts are enabled
+        let (artifact, replay_file) =
+            save_failure_trace(fuzzer, flow_calls_per_iteration, &location, &message);
+
+        // Iterations which restored the `#[init]` snapshot are reproduced by their
+        // artifact, their seed alone executes the `#[init]` method with other values
+        let mut reproducer = Vec::new();
+        if init_seed.is_none() || artifact.is_none() {
+            reproducer.push(format!("seed: {}", seed));
+        }
+        if let (Some(init_seed), None) = (init_seed, &artifact) {
+            reproducer.push(format!("init snapshot seed: {}", hex::encode(init_seed)));
+        }
+        if let Some(artifact) = artifact {
+            reproducer.push(format!("artifact: {}", artifact.display()));
+        }
+        if let Some(replay_file) = replay_file {
+            reproducer.push(format!("replay: {}", replay_file.display()));
+        }
+        let panic_msg = format!(
+            "{} {}: {} ({})",
+            failure,
+            location,
+            message,
+            reproducer.join(", ")
+        );
+        let _ = FIRST_FAILURE.set(panic_msg.clone());
+
+        if timeout.is_some() && std::env::var(config::ENV_FUZZ_EXIT_ON_TIMEOUT).is_ok() {
+            fuzzer.trident_mut().stop_signal().stop(StopReason::Timeout);
+        }
 
-        format!(
-            "Assertion failed at {}: {} (seed: {})",
-            location, message, seed
-        )
+        Some(panic_msg)
+    }
+
+    /// Reports the first failure if fail-fast mode stopped the fuzzing.
+    fn report_fail_fast(fail_fast: bool, panic_occurred: bool) {
+        if fail_fast && panic_occurred {
+            if let Some(failure) = FIRST_FAILURE.get() {
+                println!("Fail-fast: fuzzing stopped after the first failure");
+                println!("{}", failure);
+            }
+        }
     }
 
     /// Determines the exit code based on panic status and configuration.
-    /// Returns 99 if with_exit_code is enabled and panics occurred, otherwise uses metrics exit code.
+    /// Returns 98 if a timed out iteration stopped the session. Returns 99 if
+    /// with_exit_code is enabled and panics occurred (98 if all failures were timeouts),
+    /// otherwise uses metrics exit code.
     fn determine_exit_code(
         with_exit_code: bool,
         panic_occurred: bool,
+        stop_reason: Option<StopReason>,
         fuzzing_data: &TridentFuzzingData,
     ) -> i32 {
-        if with_exit_code {
+        if stop_reason == Some(StopReason::Timeout) {
+            TIMEOUT_EXIT_CODE
+        } else if with_exit_code {
             // When exit code mode is enabled, return 99 if any panics occurred
-            if panic_occurred || fuzzing_data.get_exit_code() != 0 {
+            if fuzzing_data.has_only_timeout_findings() {
+                TIMEOUT_EXIT_CODE
+            } else if panic_occurred || fuzzing_data.get_exit_code() != 0 {
                 99
             } else {
                 0
@@ -197,6 +458,29 @@ pub trait FlowExecutor: Send + 'static + Sized {
         }
     }
 
+    /// Gets the fuzzing duration from environment variable (in seconds),
+    /// falling back to the fixed number of iterations.
+    fn get_budget(iterations: u64) -> FuzzingBudget {
+        match std::env::var(config::ENV_FUZZ_DURATION) {
+            Ok(seconds) => {
+                let seconds = seconds
+                    .parse::<u64>()
+                    .unwrap_or_else(|_| panic!("Invalid fuzzing duration in seconds: {}", seconds));
+                FuzzingBudget::duration(Duration::from_secs(seconds))
+            }
+            Err(_) => FuzzingBudget::Iterations(iterations),
+        }
+    }
+
+    /// Gets the per-iteration timeout from environment variable (in milliseconds).
+    fn get_iteration_timeout() -> Option<Duration> {
+        let millis = std::env::var(config::ENV_FUZZ_ITERATION_TIMEOUT).ok()?;
+        let millis = millis
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("Invalid iteration timeout in milliseconds: {}", millis));
+        Some(Duration::from_millis(millis))
+    }
+
     /// Parses a hex-encoded seed string into a byte array.
     /// Validates that the seed is exactly the required size.
     fn parse_hex_seed(seed_hex: &str) -> [u8; config::SEED_SIZE] {
@@ -226,68 +510,202 @@ pub trait FlowExecutor: Send + 'static + Sized {
         seed
     }
 
-    /// Outputs fuzzing metrics (JSON, dashboard, etc.) if metrics are enabled.
-    fn output_metrics_if_enabled(fuzzing_data: &TridentFuzzingData) {
-        if std::env::var(config::ENV_FUZZING_METRICS).is_ok() {
-            if let Err(e) = fuzzing_data.generate() {
-                eprintln!("Warning: Failed to generate metrics: {}", e);
+    /// Loads the persistent corpus if a corpus directory is configured,
+    /// otherwise starts with an empty in-memory corpus.
+    fn load_corpus() -> Corpus {
+        let Ok(directory) = std::env::var(config::ENV_FUZZ_CORPUS) else {
+            return Corpus::default();
+        };
+
+        match Corpus::load(&directory) {
+            Ok(mut corpus) => {
+                // Workers of a distributed campaign share the corpus directory
+                if SyncDir::from_env().is_some() {
+                    corpus = corpus.with_sync(CORPUS_SYNC_INTERVAL);
+                }
+                println!(
+                    "Loaded {} corpus inputs from {}",
+                    corpus.pending(),
+                    directory
+                );
+                corpus
+            }
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to load corpus from {}: {}, starting with an empty corpus",
+                    directory, e
+                );
+                Corpus::default()
             }
         }
     }
 
     /// Executes fuzzing in a single thread.
     /// This is used for debug mode, small iteration counts, or when only one thread is available.
-    fn fuzz_single_threaded(iterations: u64, flow_calls_per_iteration: u64) {
-        let mut fuzzer = Self::new();
+    ///
+    /// With an iteration timeout, the iterations run in a separate thread, so the fuzzing
+    /// data are reported even if an iteration hangs.
+    fn fuzz_single_threaded(budget: FuzzingBudget, flow_calls_per_iteration: u64) {
         let is_debug_mode = std::env::var(config::ENV_FUZZ_DEBUG).is_ok();
         let with_exit_code = std::env::var(config::ENV_WITH_EXIT_CODE).is_ok();
-        let mut panic_occurred = false; // Simple bool since we're single-threaded
-
-        // Configure debug seed if in debug mode
-        if is_debug_mode {
-            let debug_seed_hex = std::env::var(config::ENV_FUZZ_DEBUG).unwrap();
-            let debug_seed = Self::parse_hex_seed(&debug_seed_hex);
-            println!("Using debug seed: {}", debug_seed_hex);
-            fuzzer.trident_mut().set_master_seed_for_debug(debug_seed);
-        }
+        let fail_fast = std::env::var(config::ENV_FUZZ_FAIL_FAST).is_ok();
+        let stop = StopSignal::default();
+        let completed_iterations = Arc::new(AtomicU64::new(0));
+        let iteration_timeout = Self::get_iteration_timeout();
 
         // Setup progress bar (disabled in debug mode for cleaner output)
         let pb = if is_debug_mode {
             None
         } else {
-            let total_flow_calls = iterations * flow_calls_per_iteration;
-            let pb = indicatif::ProgressBar::new(total_flow_calls);
-            pb.set_style(
-                indicatif::ProgressStyle::with_template(
-                    "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos}/{len} ({percent}%) [{eta_precise}] {msg}"
-                )
-                .unwrap()
-                .progress_chars("#>-"),
-            );
+            let pb = budget.progress_bar("", flow_calls_per_iteration);
             pb.set_message(format!(
-                "Fuzzing {} iterations with {} flow calls each...",
-                iterations, flow_calls_per_iteration
+                "Fuzzing {} with {} flow calls each...",
+                budget.describe(),
+                flow_calls_per_iteration
             ));
             Some(pb)
         };
 
+        let workload = {
+            let pb = pb.clone();
+            let stop = stop.clone();
+            let completed_iterations = completed_iterations.clone();
+            move || {
+                Self::run_single_threaded_workload(
+                    budget,
+                    flow_calls_per_iteration,
+                    pb,
+                    stop,
+                    completed_iterations,
+                    iteration_timeout,
+                )
+            }
+        };
+        let (fuzzing_data, panic_occurred, hung) = if iteration_timeout.is_some() {
+            match wait_for_fuzzer(thread::spawn(workload), None) {
+                FuzzerOutcome::Finished(Ok((fuzzing_data, panic_occurred))) => {
+                    (fuzzing_data, panic_occurred, false)
+                }
+                FuzzerOutcome::Finished(Err(err)) => std::panic::resume_unwind(err),
+                FuzzerOutcome::Hung(fuzzing_data) => (fuzzing_data, true, true),
+            }
+        } else {
+            let (fuzzing_data, panic_occurred) = workload();
+            (fuzzing_data, panic_occurred, false)
+        };
+
+        // Finalize progress bar
+        if let Some(pb) = pb {
+            pb.finish_with_message("Fuzzing completed!");
+        }
+        budget.print_summary(completed_iterations.load(Ordering::Relaxed));
+        Self::report_fail_fast(fail_fast, panic_occurred);
+
+        // Generate metrics if enabled
+        output_metrics_if_enabled(&fuzzing_data);
+
+        // Exit with appropriate code if exit code mode is enabled, a session stopped by
+        // a timeout exits with its own exit code and a hung iteration must not keep
+        // the process running
+        let exit_code =
+            Self::determine_exit_code(with_exit_code, panic_occurred, stop.reason(), &fuzzing_data);
+        if with_exit_code || hung || stop.reason() == Some(StopReason::Timeout) {
+            std::process::exit(exit_code);
+        }
+    }
+
+    /// Runs the fuzzing workload of `fuzz_single_threaded`, returns the fuzzing data
+    /// and whether a panic occurred
+    fn run_single_threaded_workload(
+        budget: FuzzingBudget,
+        mut flow_calls_per_iteration: u64,
+        pb: Option<indicatif::ProgressBar>,
+        stop: StopSignal,
+        completed: Arc<AtomicU64>,
+        iteration_timeout: Option<Duration>,
+    ) -> (TridentFuzzingData, bool) {
+        let mut fuzzer = Self::new();
+        fuzzer.trident_mut().set_stop_signal(stop.clone());
+        let is_debug_mode = std::env::var(config::ENV_FUZZ_DEBUG).is_ok();
+        let mut panic_occurred = false; // Simple bool since we're single-threaded
+
+        // Configure debug seed if in debug mode, either directly or from a crash artifact
+        if is_debug_mode {
+            let debug_seed = std::env::var(config::ENV_FUZZ_DEBUG).unwrap();
+            let artifact_path = std::path::Path::new(&debug_seed);
+            if artifact_path.is_file() {
+                let artifact = CrashArtifact::load(artifact_path).unwrap_or_else(|e| {
+                    panic!("Failed to load crash artifact {}: {}", debug_seed, e)
+                });
+                if let Some(init_seed) = Trident::crash_artifact_init_seed(&artifact) {
+                    println!(
+                        "Taking the #[init] snapshot with seed: {}",
+                        hex::encode(init_seed)
+                    );
+                    take_init_snapshot(&mut fuzzer, init_seed);
+                }
+                fuzzer
+                    .trident_mut()
+                    .load_crash_artifact_for_debug(&artifact);
+                // Minimized artifacts define the exact number of flow calls
+                if let Some(steps) = &artifact.steps {
+                    flow_calls_per_iteration =
+                        steps.iter().filter(|step| step.selection.is_some()).count() as u64;
+                }
+                println!(
+                    "Using crash artifact: {} (seed: {})",
+                    debug_seed, artifact.iteration_seed
+                );
+            } else {
+                let debug_seed_bytes = Self::parse_hex_seed(&debug_seed);
+                println!("Using debug seed: {}", debug_seed);
+                fuzzer
+                    .trident_mut()
+                    .set_master_seed_for_debug(debug_seed_bytes);
+            }
+        }
+
+        // Debugged iterations are selected with the weights of their crash artifact, as
+        // learned weights depend on all previous iterations
+        if !is_debug_mode && std::env::var(config::ENV_FUZZ_ADAPTIVE).is_ok() {
+            fuzzer.trident_mut().enable_adaptive_weights();
+        }
+
+        // Debugged iterations execute the `#[init]` method with their own seed, unless
+        // the snapshot was taken from their crash artifact
+        if !is_debug_mode {
+            fuzzer.trident_mut().enable_init_snapshot();
+        }
+
+        if let Some(timeout) = iteration_timeout {
+            let exit_on_timeout = std::env::var(config::ENV_FUZZ_EXIT_ON_TIMEOUT).is_ok();
+            fuzzer
+                .trident_mut()
+                .enable_watchdog(timeout, exit_on_timeout);
+        }
+
         // Main fuzzing loop: execute flows, catch panics, and track progress
-        for i in 0..iterations {
-            // Catch panics from user code (assertions, invariants, etc.)
+        let mut completed_iterations = 0;
+        while !budget.is_exhausted(completed_iterations) {
+            // Catch panics from user code (assertions, invariants, timeouts, etc.)
+            fuzzer.trident_mut().start_iteration_watch();
             let panic_result = catch_unwind(AssertUnwindSafe(|| {
                 let _ = fuzzer.execute_flows(flow_calls_per_iteration);
             }));
+            fuzzer.trident_mut().stop_iteration_watch();
 
             // Handle any panics that occurred
             if let Err(panic_err) = panic_result {
                 panic_occurred = true;
-                let panic_msg = Self::handle_panic(&panic_err, &mut fuzzer, None);
-
                 // Display panic message via progress bar or stderr
-                if let Some(ref pb) = pb {
-                    pb.println(panic_msg);
-                } else {
-                    eprintln!("{}", panic_msg);
+                if let Some(panic_msg) =
+                    Self::handle_panic(&panic_err, &mut fuzzer, flow_calls_per_iteration, None)
+                {
+                    if let Some(ref pb) = pb {
+                        pb.println(panic_msg);
+                    } else {
+                        eprintln!("{}", panic_msg);
+                    }
                 }
             }
 
@@ -295,92 +713,108 @@ pub trait FlowExecutor: Send + 'static + Sized {
             fuzzer.trident_mut().next_iteration();
             fuzzer.reset_fuzz_accounts();
 
+            completed_iterations += 1;
+            completed.fetch_add(1, Ordering::Relaxed);
+
             // Handle coverage profiling if enabled
-            Self::handle_coverage_if_enabled(&mut fuzzer, i + 1);
+            Self::handle_coverage_if_enabled(&mut fuzzer, completed_iterations);
 
             // Update progress bar
             if let Some(ref pb) = pb {
-                pb.inc(flow_calls_per_iteration);
-                pb.set_message(format!("Iteration {}/{} completed", i + 1, iterations));
+                budget.report_progress(pb, flow_calls_per_iteration);
+                match budget {
+                    FuzzingBudget::Iterations(iterations) => pb.set_message(format!(
+                        "Iteration {}/{} completed",
+                        completed_iterations, iterations
+                    )),
+                    FuzzingBudget::Duration { .. } => {
+                        pb.set_message(format!("Iteration {} completed", completed_iterations))
+                    }
+                }
             }
-        }
 
-        // Finalize progress bar
-        if let Some(pb) = pb {
-            pb.finish_with_message("Fuzzing completed!");
+            // Stop after the first failure in fail-fast mode, or once a timeout
+            // stopped the session
+            if stop.is_stopped() {
+                break;
+            }
         }
 
-        // Generate metrics if enabled
-        let fuzzing_data = fuzzer.trident_mut().get_fuzzing_data();
-        Self::output_metrics_if_enabled(&fuzzing_data);
-
-        // Exit with appropriate code if exit code mode is enabled
-        if with_exit_code {
-            let exit_code =
-                Self::determine_exit_code(with_exit_code, panic_occurred, &fuzzing_data);
-            std::process::exit(exit_code);
-        }
+        (fuzzer.trident_mut().get_fuzzing_data(), panic_occurred)
     }
 
     /// Executes fuzzing across multiple threads for better performance.
     /// Each thread runs a subset of iterations with its own fuzzer instance.
+    /// If a corpus is provided, iterations are guided by execution feedback and the corpus is shared by all threads.
     fn fuzz_parallel(
-        iterations: u64,
+        budget: FuzzingBudget,
         flow_calls_per_iteration: u64,
         num_threads: usize,
         master_seed: [u8; 32],
+        corpus: Option<Arc<Mutex<Corpus>>>,
     ) {
-        let iterations_per_thread = iterations / num_threads as u64;
-        let total_flow_calls = iterations * flow_calls_per_iteration;
+        let thread_budget = budget.per_thread(num_threads);
+        // Workers of a distributed campaign fuzz the iterations of their own master seed,
+        // their fuzzing data keep the master seed of the campaign
+        let threads_master_seed =
+            SyncDir::from_env().map_or(master_seed, |sync| sync.worker_master_seed(master_seed));
         let with_exit_code = std::env::var(config::ENV_WITH_EXIT_CODE).is_ok();
+        let fail_fast = std::env::var(config::ENV_FUZZ_FAIL_FAST).is_ok();
         let panic_occurred = Arc::new(AtomicBool::new(false)); // Shared across threads
+        let stop = StopSignal::default(); // Shared across threads
+        let completed_iterations = Arc::new(AtomicU64::new(0)); // Shared across threads
 
         // Setup shared progress bar
-        let main_pb = indicatif::ProgressBar::new(total_flow_calls);
-        main_pb.set_style(
-            indicatif::ProgressStyle::with_template(
-                "Overall: {spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos}/{len} ({percent}%) [{eta_precise}] {msg}"
-            )
-            .unwrap()
-            .progress_chars("#>-"),
-        );
+        let main_pb = budget.progress_bar("Overall: ", flow_calls_per_iteration);
         main_pb.set_message(format!(
-            "Fuzzing with {} threads - {} iterations with {} flow calls each",
-            num_threads, iterations, flow_calls_per_iteration
+            "Fuzzing with {} threads - {} with {} flow calls each",
+            num_threads,
+            budget.describe(),
+            flow_calls_per_iteration
         ));
 
         // Spawn worker threads
         let mut handles = Vec::new();
         for thread_id in 0..num_threads {
-            let thread_iterations = iterations_per_thread;
-            if thread_iterations == 0 {
+            if thread_budget.is_empty() {
                 continue; // Skip threads with no work
             }
 
             let main_pb_clone = main_pb.clone();
             let panic_occurred_clone = panic_occurred.clone();
+            let stop_clone = stop.clone();
+            let completed_iterations_clone = completed_iterations.clone();
+            let corpus_clone = corpus.clone();
             let handle = thread::spawn(move || -> TridentFuzzingData {
                 Self::run_thread_workload(
-                    master_seed,
+                    threads_master_seed,
                     thread_id,
-                    thread_iterations,
+                    thread_budget,
                     flow_calls_per_iteration,
                     main_pb_clone,
                     panic_occurred_clone,
+                    stop_clone,
+                    completed_iterations_clone,
+                    corpus_clone,
                 )
             });
 
-            handles.push(handle);
+            handles.push((thread_id, handle));
         }
 
-        // Collect results from all threads
+        // Collect results from all threads, threads hung in an iteration are abandoned
+        // with the fuzzing data they shared with the watchdog last
         let mut fuzzing_data = TridentFuzzingData::with_master_seed(master_seed);
-        for handle in handles {
-            match handle.join() {
-                Ok(thread_metrics) => {
+        for (thread_id, handle) in handles {
+            match wait_for_fuzzer(handle, Some(thread_id)) {
+                FuzzerOutcome::Hung(thread_metrics) => {
+                    panic_occurred.store(true, Ordering::Relaxed);
+                    fuzzing_data._merge(thread_metrics);
+                }
+                FuzzerOutcome::Finished(Ok(thread_metrics)) => {
                     fuzzing_data._merge(thread_metrics);
                 }
-                Err(err) => {
+                FuzzerOutcome::Finished(Err(err)) => {
                     // This should rarely happen since we catch panics inside threads
                     // Only occurs if the thread itself crashes (not user code)
                     eprintln!("Warning: Thread failed to join (not a fuzz test panic)");
@@ -395,15 +829,26 @@ pub trait FlowExecutor: Send + 'static + Sized {
         }
 
         main_pb.finish_with_message("Parallel fuzzing completed!");
+        budget.print_summary(completed_iterations.load(Ordering::Relaxed));
+        Self::report_fail_fast(fail_fast, panic_occurred.load(Ordering::Relaxed));
 
         // Determine and set exit code
         let exit_code = Self::determine_exit_code(
             with_exit_code,
             panic_occurred.load(Ordering::Relaxed),
+            stop.reason(),
             &fuzzing_data,
         );
 
-        Self::output_metrics_if_enabled(&fuzzing_data);
+        report_fuzzing_data(&fuzzing_data);
+        if let Some(corpus) = corpus {
+            let corpus = corpus.lock().unwrap();
+            println!(
+                "Guided fuzzing finished with {} corpus inputs reaching {} unique execution features",
+                corpus.len(),
+                corpus.features()
+            );
+        }
         println!("MASTER SEED used: {:?}", &hex::encode(master_seed));
 
         std::process::exit(exit_code);
@@ -411,53 +856,118 @@ pub trait FlowExecutor: Send + 'static + Sized {
 
     /// Runs the fuzzing workload for a single thread.
     /// This is extracted to reduce complexity in fuzz_parallel.
+    #[allow(clippy::too_many_arguments)]
     fn run_thread_workload(
         master_seed: [u8; 32],
         thread_id: usize,
-        thread_iterations: u64,
+        budget: FuzzingBudget,
         flow_calls_per_iteration: u64,
         progress_bar: indicatif::ProgressBar,
         panic_occurred: Arc<AtomicBool>,
+        stop: StopSignal,
+        completed_iterations: Arc<AtomicU64>,
+        corpus: Option<Arc<Mutex<Corpus>>>,
     ) -> TridentFuzzingData {
         let mut fuzzer = Self::new();
         fuzzer
             .trident_mut()
             .set_master_seed_and_thread_id(master_seed, thread_id);
+        fuzzer.trident_mut().set_stop_signal(stop.clone());
+
+        // Mutations are driven by their own rng so they do not shift the iteration seeds
+        let mut mutation_rng = SmallRng::from_seed(fuzzer.trident_mut().get_current_seed());
 
         // Track progress updates to avoid excessive bar updates
         let mut last_update = Instant::now();
         let mut local_counter = 0u64;
 
+        if let Some(timeout) = Self::get_iteration_timeout() {
+            let exit_on_timeout = std::env::var(config::ENV_FUZZ_EXIT_ON_TIMEOUT).is_ok();
+            fuzzer
+                .trident_mut()
+                .enable_watchdog(timeout, exit_on_timeout);
+        }
+
+        // Every thread learns its own flow weights
+        if std::env::var(config::ENV_FUZZ_ADAPTIVE).is_ok() {
+            fuzzer.trident_mut().enable_adaptive_weights();
+        }
+
+        // Guided iterations are replayed from their recorded random values, including
+        // the values drawn by the `#[init]` method
+        if corpus.is_none() {
+            fuzzer.trident_mut().enable_init_snapshot();
+        }
+
         // Execute iterations for this thread
-        for i in 0..thread_iterations {
-            // Catch panics from user code (assertions, invariants, etc.)
+        let mut thread_iterations = 0;
+        while !budget.is_exhausted(thread_iterations) {
+            // In fail-fast mode, all threads stop once any of them fails, a timed out
+            // iteration stops all threads if `exit_on_timeout` is enabled. The running
+            // iterations of other threads skip their remaining flow calls
+            if stop.is_stopped() {
+                break;
+            }
+
+            // In guided mode, start from a mutated corpus input (or a fresh one)
+            if let Some(corpus) = &corpus {
+                let input = corpus.lock().unwrap().next_input(&mut mutation_rng);
+                fuzzer.trident_mut().begin_guided_iteration(input);
+            }
+
+            // Catch panics from user code (assertions, invariants, timeouts, etc.)
+            fuzzer.trident_mut().start_iteration_watch();
             let panic_result = catch_unwind(AssertUnwindSafe(|| {
                 let _ = fuzzer.execute_flows(flow_calls_per_iteration);
             }));
+            fuzzer.trident_mut().stop_iteration_watch();
 
             // Handle any panics that occurred
             if let Err(panic_err) = panic_result {
-                let panic_msg = Self::handle_panic(&panic_err, &mut fuzzer, Some(&panic_occurred));
-                progress_bar.println(panic_msg);
+                if let Some(panic_msg) = Self::handle_panic(
+                    &panic_err,
+                    &mut fuzzer,
+                    flow_calls_per_iteration,
+                    Some(&panic_occurred),
+                ) {
+                    progress_bar.println(panic_msg);
+                }
+            }
+
+            // Keep the iteration if it reached new execution features
+            if let Some(corpus) = &corpus {
+                let (input, features) = fuzzer.trident_mut().finish_guided_iteration();
+                corpus.lock().unwrap().evaluate(input, features);
             }
 
             // Prepare for next iteration
             fuzzer.trident_mut().next_iteration();
             fuzzer.reset_fuzz_accounts();
 
+            thread_iterations += 1;
+            completed_iterations.fetch_add(1, Ordering::Relaxed);
+
             // Handle coverage profiling (only thread 0 to avoid duplicate work)
             if thread_id == 0 {
-                Self::handle_coverage_if_enabled(&mut fuzzer, i + 1);
+                Self::handle_coverage_if_enabled(&mut fuzzer, thread_iterations);
             }
 
             // Batch progress updates for performance
             local_counter += flow_calls_per_iteration;
             let should_update = local_counter >= config::PROGRESS_UPDATE_INTERVAL
                 || last_update.elapsed() >= config::PROGRESS_UPDATE_DURATION
-                || i == thread_iterations - 1; // Always update on last iteration
+                || budget.is_exhausted(thread_iterations); // Always update on last iteration
 
             if should_update {
-                progress_bar.inc(local_counter);
+                if let Some(corpus) = &corpus {
+                    let corpus = corpus.lock().unwrap();
+                    progress_bar.set_message(format!(
+                        "Guided fuzzing with {} corpus inputs, {} execution features",
+                        corpus.len(),
+                        corpus.features()
+                    ));
+                }
+                budget.report_progress(&progress_bar, local_counter);
                 local_counter = 0;
                 last_update = Instant::now();
             }
@@ -465,7 +975,7 @@ pub trait FlowExecutor: Send + 'static + Sized {
 
         // Ensure any remaining progress is reported
         if local_counter > 0 {
-            progress_bar.inc(local_counter);
+            budget.report_progress(&progress_bar, local_counter);
         }
 
         fuzzer.trident_mut().get_fuzzing_data()
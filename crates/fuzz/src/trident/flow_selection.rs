@@ -0,0 +1,176 @@
+use crate::trident::Trident;
+
+/// A flow the generated flow executor can select in the current flow call
+#[doc(hidden)]
+pub struct FlowCandidate {
+    pub name: &'static str,
+    /// Weight of the flow, `1` for every flow if no weights are specified
+    pub weight: u32,
+    /// `false` if the `requires` guard of the flow is not satisfied
+    pub enabled: bool,
+    /// Flows which have to be called earlier in the iteration
+    pub after: &'static [&'static str],
+    /// Maximal number of calls of the flow per iteration
+    pub max_calls: Option<u32>,
+}
+
+impl Trident {
+    /// Checks the guard and the ordering constraints of the flow in the current iteration
+    fn is_selectable(&self, flow: &FlowCandidate) -> bool {
+        flow.enabled
+            && self.is_swarm_enabled(flow.name)
+            && flow
+                .max_calls
+                .is_none_or(|max_calls| self.iteration_flow_count(flow.name) < max_calls)
+            && flow
+                .after
+                .iter()
+                .all(|previous| self.iteration_flow_count(previous) > 0)
+    }
+
+    /// Hook for the generated flow executor, selects the index of the next flow out of `flows`
+    ///
+    /// Only flows with a satisfied guard and ordering constraints (`after`, `once`
+    /// and `max_calls`) which are not disabled by swarm mode are selected, with the
+    /// probability given by their weights, or by the learned weights if adaptive
+    /// weights are enabled. Once the session is stopped (fail-fast mode or
+    /// `exit_on_timeout`), no flow is selected, so the remaining flow calls of the
+    /// iteration are skipped.
+    ///
+    /// # Returns
+    /// Index of the selected flow, or `None` if no flow can be selected
+    #[doc(hidden)]
+    pub fn _select_flow(&mut self, flows: &[FlowCandidate]) -> Option<usize> {
+        if self.skips_remaining_flows() {
+            return None;
+        }
+        if !self.is_replaying_decisions() {
+            self.choose_swarm_flows(flows);
+        }
+        let selectable: Vec<bool> = flows.iter().map(|flow| self.is_selectable(flow)).collect();
+
+        let exists = |name: &str| {
+            flows
+                .iter()
+                .zip(&selectable)
+                .any(|(flow, selectable)| *selectable && flow.name == name)
+        };
+        if let Some(flow) = self.replay_flow_selection(exists) {
+            return flows.iter().position(|candidate| candidate.name == flow);
+        }
+
+        let weights: Vec<u32> = flows
+            .iter()
+            .zip(&selectable)
+            .map(|(flow, selectable)| {
+                if *selectable {
+                    self.adaptive_weight(flow.name, flow.weight)
+                } else {
+                    0
+                }
+            })
+            .collect();
+        let total_weight: u32 = weights.iter().sum();
+
+        // Replayed flow steps of a changed or reduced sequence may refer to flows which
+        // do not exist or cannot be selected, such steps are skipped
+        self.select_flow_step(&selectable, |trident| {
+            if total_weight == 0 {
+                return None;
+            }
+            let mut weight = trident.rng.gen_range(0..total_weight);
+            let index = weights
+                .iter()
+                .position(|flow_weight| {
+                    if weight < *flow_weight {
+                        true
+                    } else {
+                        weight -= flow_weight;
+                        false
+                    }
+                })
+                .unwrap_or_default();
+            Some(index as u64)
+        })
+        .map(|index| index as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEEDS: u8 = 32;
+
+    fn flow(
+        name: &'static str,
+        after: &'static [&'static str],
+        max_calls: Option<u32>,
+    ) -> FlowCandidate {
+        FlowCandidate {
+            name,
+            weight: 1,
+            enabled: true,
+            after,
+            max_calls,
+        }
+    }
+
+    /// Selects and records `flow_calls` flows of an iteration with the given seed
+    fn select_flows(flows: &[FlowCandidate], seed: u8, flow_calls: usize) -> Vec<&'static str> {
+        let mut trident = Trident::for_tests();
+        trident.set_master_seed_for_debug([seed; 32]);
+        (0..flow_calls)
+            .map_while(|_| {
+                let name = flows[trident._select_flow(flows)?].name;
+                trident._record_flow(name);
+                Some(name)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_flow_is_not_selected_before_after_flows() {
+        let flows = [
+            flow("withdraw", &["deposit"], None),
+            flow("close", &["deposit", "withdraw"], None),
+            flow("deposit", &[], None),
+        ];
+        for seed in 0..SEEDS {
+            let selected = select_flows(&flows, seed, 20);
+            let first = |name| selected.iter().position(|flow| *flow == name);
+            assert_eq!(selected[0], "deposit");
+            if let Some(close) = first("close") {
+                assert!(first("withdraw").is_some_and(|withdraw| withdraw < close));
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_calls_are_respected() {
+        let flows = [
+            flow("deposit", &[], Some(2)),
+            flow("withdraw", &[], Some(1)),
+        ];
+        for seed in 0..SEEDS {
+            let selected = select_flows(&flows, seed, 10);
+            // No flow is selected once all flows reached their maximal number of calls
+            assert_eq!(selected.len(), 3);
+            assert_eq!(
+                selected.iter().filter(|flow| **flow == "deposit").count(),
+                2
+            );
+        }
+    }
+
+    #[test]
+    fn test_disabled_flow_is_not_selected() {
+        let mut flows = [flow("deposit", &[], None), flow("withdraw", &[], None)];
+        flows[1].enabled = false;
+        for seed in 0..SEEDS {
+            assert!(select_flows(&flows, seed, 10)
+                .iter()
+                .all(|flow| *flow == "deposit"));
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+
+use trident_fuzz_metrics::CrashFlowStep;
+
+use crate::trident::Trident;
+
+/// A single flow call of an iteration together with the RNG values it consumed
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RecordedStep {
+    pub(crate) flow: String,
+    /// Index of the selected flow, `None` for `#[init]` and `#[end]` flows
+    pub(crate) selection: Option<u64>,
+    /// RNG values consumed by the flow itself
+    pub(crate) input: Vec<u8>,
+}
+
+impl RecordedStep {
+    pub(crate) fn is_selected(&self) -> bool {
+        self.selection.is_some()
+    }
+}
+
+impl From<&RecordedStep> for CrashFlowStep {
+    fn from(step: &RecordedStep) -> Self {
+        Self {
+            flow: step.flow.clone(),
+            selection: step.selection,
+            input: hex::encode(&step.input),
+        }
+    }
+}
+
+impl From<&CrashFlowStep> for RecordedStep {
+    fn from(step: &CrashFlowStep) -> Self {
+        Self {
+            flow: step.flow.clone(),
+            selection: step.selection,
+            input: hex::decode(&step.input)
+                .unwrap_or_else(|_| panic!("Invalid flow step input: {}", step.input)),
+        }
+    }
+}
+
+/// Splits an iteration into flow calls, or executes a given sequence of flow calls
+///
+/// While recording, the RNG values consumed by each flow are stored separately,
+/// so any subsequence of the flow calls can be replayed with the same values.
+#[derive(Default)]
+pub(crate) enum FlowSteps {
+    #[default]
+    Off,
+    Record {
+        steps: Vec<RecordedStep>,
+        step_start: usize,
+        selected: bool,
+    },
+    Replay {
+        plan: VecDeque<RecordedStep>,
+        selected: bool,
+        /// A replayed flow could not be selected in the current flow call
+        diverged: bool,
+    },
+}
+
+impl Trident {
+    /// Starts splitting the iteration into flow steps, RNG recording has to be enabled
+    pub(crate) fn record_flow_steps(&mut self) {
+        self.flow_steps = FlowSteps::Record {
+            steps: Vec::new(),
+            step_start: self.recorded_len(),
+            selected: false,
+        };
+    }
+
+    /// Executes the given flow steps in the next iteration
+    pub(crate) fn replay_flow_steps(&mut self, steps: &[RecordedStep]) {
+        self.flow_steps = FlowSteps::Replay {
+            plan: steps.iter().cloned().collect(),
+            selected: false,
+            diverged: false,
+        };
+    }
+
+    /// Whether a replayed flow step was skipped because its flow could not be selected
+    pub(crate) fn flow_steps_diverged(&self) -> bool {
+        matches!(self.flow_steps, FlowSteps::Replay { diverged: true, .. })
+    }
+
+    /// Stops recording or replaying flow steps
+    ///
+    /// # Returns
+    /// The recorded flow steps (empty if flow steps were not recorded)
+    pub(crate) fn take_flow_steps(&mut self) -> Vec<RecordedStep> {
+        self.finish_flow_step();
+        match std::mem::take(&mut self.flow_steps) {
+            FlowSteps::Record { steps, .. } => steps,
+            _ => Vec::new(),
+        }
+    }
+
+    /// Marks the start of a flow (called from `_record_flow`)
+    pub(crate) fn begin_flow_step(&mut self, flow_name: &str) {
+        let recorded_len = self.recorded_len();
+        match &mut self.flow_steps {
+            FlowSteps::Off => {}
+            FlowSteps::Record {
+                steps,
+                step_start,
+                selected,
+            } => {
+                if std::mem::take(selected) {
+                    // The step was already created by the flow selection
+                    if let Some(step) = steps.last_mut() {
+                        step.flow = flow_name.to_string();
+                    }
+                } else {
+                    Self::finish_recorded_step(steps, *step_start, self.rng.recorded_input());
+                    steps.push(RecordedStep {
+                        flow: flow_name.to_string(),
+                        selection: None,
+                        input: Vec::new(),
+                    });
+                    *step_start = recorded_len;
+                }
+            }
+            FlowSteps::Replay { plan, selected, .. } => {
+                if !std::mem::take(selected) {
+                    if let Some(step) = plan.pop_front() {
+                        self.rng.set_input(step.input);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws the index of the next selected flow, recording or replaying it as a flow step
+    ///
+    /// Replayed flows have to be selectable under the constraints of the current flow
+    /// call, otherwise the step is skipped and the replay is marked as diverged.
+    ///
+    /// # Arguments
+    /// * `selectable` - Whether each flow can be selected in the current flow call
+    /// * `draw` - Draws the index of a selectable flow, `None` if there is none
+    pub(crate) fn select_flow_step(
+        &mut self,
+        selectable: &[bool],
+        draw: impl FnOnce(&mut Self) -> Option<u64>,
+    ) -> Option<u64> {
+        match &mut self.flow_steps {
+            FlowSteps::Off => draw(self),
+            FlowSteps::Record { .. } => {
+                self.finish_flow_step();
+                let selection = draw(self)?;
+                let recorded_len = self.recorded_len();
+                if let FlowSteps::Record {
+                    steps,
+                    step_start,
+                    selected,
+                } = &mut self.flow_steps
+                {
+                    steps.push(RecordedStep {
+                        flow: String::new(),
+                        selection: Some(selection),
+                        input: Vec::new(),
+                    });
+                    *step_start = recorded_len;
+                    *selected = true;
+                }
+                Some(selection)
+            }
+            FlowSteps::Replay {
+                plan,
+                selected,
+                diverged,
+            } => {
+                match plan.front().and_then(|step| step.selection) {
+                    Some(selection) => {
+                        let step = plan.pop_front().unwrap();
+                        if !selectable.get(selection as usize).copied().unwrap_or(false) {
+                            *diverged = true;
+                            return None;
+                        }
+                        *selected = true;
+                        self.rng.set_input(step.input);
+                        Some(selection)
+                    }
+                    // The plan does not cover this flow call
+                    None => draw(self),
+                }
+            }
+        }
+    }
+
+    fn finish_flow_step(&mut self) {
+        if let FlowSteps::Record {
+            steps, step_start, ..
+        } = &mut self.flow_steps
+        {
+            Self::finish_recorded_step(steps, *step_start, self.rng.recorded_input());
+        }
+    }
+
+    fn finish_recorded_step(
+        steps: &mut [RecordedStep],
+        step_start: usize,
+        recorded: Option<&[u8]>,
+    ) {
+        if let (Some(step), Some(recorded)) = (steps.last_mut(), recorded) {
+            step.input = recorded.get(step_start..).unwrap_or_default().to_vec();
+        }
+    }
+
+    fn recorded_len(&self) -> usize {
+        self.rng.recorded_input().map_or(0, <[u8]>::len)
+    }
+}
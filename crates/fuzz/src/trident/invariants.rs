@@ -0,0 +1,49 @@
+use crate::trident::Trident;
+
+/// `#[invariant]` method being checked, reported if the invariant fails
+pub(crate) struct InvariantCheck {
+    pub(crate) invariant: String,
+    /// Flow after which the invariant is checked
+    pub(crate) flow: String,
+}
+
+impl Trident {
+    /// Marks the start of an invariant check (called by the generated flow executor)
+    #[doc(hidden)]
+    pub fn _begin_invariant(&mut self, invariant: &str, flow: &str) {
+        self.invariant = Some(InvariantCheck {
+            invariant: invariant.to_string(),
+            flow: flow.to_string(),
+        });
+    }
+
+    /// Marks the end of an invariant check (called by the generated flow executor)
+    #[doc(hidden)]
+    pub fn _end_invariant(&mut self) {
+        self.invariant = None;
+    }
+
+    /// Takes the invariant check which was interrupted by a panic
+    pub(crate) fn take_failed_invariant(&mut self) -> Option<InvariantCheck> {
+        self.invariant.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interrupted_invariant_is_taken() {
+        let mut trident = Trident::for_tests();
+        trident._begin_invariant("balance_matches", "deposit");
+        trident._end_invariant();
+        assert!(trident.take_failed_invariant().is_none());
+
+        trident._begin_invariant("balance_matches", "withdraw");
+        let failed = trident.take_failed_invariant().unwrap();
+        assert_eq!(failed.invariant, "balance_matches");
+        assert_eq!(failed.flow, "withdraw");
+        assert!(trident.take_failed_invariant().is_none());
+    }
+}
@@ -0,0 +1,116 @@
+use solana_sdk::account::ReadableAccount;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+use trident_config::TridentConfig;
+use trident_svm::prelude::solana_svm::transaction_processing_result::ProcessedTransaction;
+use trident_svm::prelude::TridentTransactionProcessingResult;
+
+use crate::trident::Trident;
+
+/// Lamport conservation invariant, enabled by `lamport_conservation` in `[fuzz.invariants]`
+///
+/// The sum of lamports over all accounts of a transaction after the transaction must equal
+/// the sum before it, minus the fee of the transaction.
+pub(crate) struct LamportConservation {
+    enabled: bool,
+}
+
+impl LamportConservation {
+    pub(crate) fn new(config: &TridentConfig) -> Self {
+        Self {
+            enabled: config.get_lamport_conservation(),
+        }
+    }
+}
+
+impl Trident {
+    /// Lamports of every account of the transaction, if the lamport conservation invariant is enabled
+    pub(crate) fn lamports_before_transaction(
+        &self,
+        instructions: &[Instruction],
+    ) -> Option<Vec<(Pubkey, u64)>> {
+        if !self.lamport_conservation.enabled {
+            return None;
+        }
+        let message = Message::new(instructions, Some(&self.payer().pubkey()));
+        Some(
+            message
+                .account_keys
+                .into_iter()
+                .map(|address| (address, self.lamports_of(&address)))
+                .collect(),
+        )
+    }
+
+    /// Lamport conservation invariant, records a finding of the transaction if the
+    /// transaction created or destroyed lamports
+    ///
+    /// Same as the account state invariants, the finding does not stop the iteration.
+    pub(crate) fn check_lamport_conservation(
+        &mut self,
+        log_as: Option<&str>,
+        lamports_before: Option<Vec<(Pubkey, u64)>>,
+        processing_data: &TridentTransactionProcessingResult,
+    ) {
+        let Some(lamports_before) = lamports_before else {
+            return;
+        };
+        // Only successful transactions are committed, including their fee
+        let fee = match &processing_data.get_result().processing_results[0] {
+            Ok(ProcessedTransaction::Executed(executed_transaction))
+                if executed_transaction.execution_details.status.is_ok() =>
+            {
+                executed_transaction
+                    .loaded_transaction
+                    .fee_details
+                    .total_fee()
+            }
+            _ => 0,
+        };
+
+        let deltas: Vec<(Pubkey, i128)> = lamports_before
+            .iter()
+            .map(|(address, before)| {
+                let after = self.lamports_of(address);
+                (*address, after as i128 - *before as i128)
+            })
+            .collect();
+        let total_delta: i128 = deltas.iter().map(|(_, delta)| delta).sum();
+
+        if total_delta != -(fee as i128) {
+            let account_deltas = deltas
+                .iter()
+                .filter(|(_, delta)| *delta != 0)
+                .map(|(address, delta)| format!("account {} changed by {:+}", address, delta))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let violation = format!(
+                "the accounts changed by {:+} lamports with a fee of {} lamports: {}",
+                total_delta, fee, account_deltas
+            );
+            if std::env::var("TRIDENT_FUZZ_DEBUG").is_ok() {
+                trident_svm::prelude::trident_svm_log::log_message(
+                    &format!("LAMPORT CONSERVATION VIOLATED: {}", violation),
+                    trident_svm::prelude::Level::Error,
+                );
+            }
+            let seed = self.rng.get_seed();
+            let flow_calls = self.iteration_flow_calls();
+            self.fuzzing_data.add_lamport_conservation_finding(
+                log_as.unwrap_or("without name"),
+                &violation,
+                &seed,
+                flow_calls,
+            );
+        }
+    }
+
+    fn lamports_of(&self, address: &Pubkey) -> u64 {
+        self.client
+            .get_account(address)
+            .map(|account| account.lamports())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,280 @@
+use std::panic::catch_unwind;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
+
+use trident_fuzz_metrics::collapse_digits;
+use trident_fuzz_metrics::types::Seed;
+use trident_fuzz_metrics::CrashArtifact;
+use trident_fuzz_metrics::CrashFlowStep;
+
+use crate::trident::flow_executor::FlowExecutor;
+use crate::trident::flow_executor::PANIC_LOCATION;
+use crate::trident::flow_steps::RecordedStep;
+use crate::trident::snapshot::take_init_snapshot;
+use crate::trident::Trident;
+
+/// Panic of a failing iteration
+///
+/// Two panics are considered the same if they occurred at the same location
+/// and their messages differ at most in numbers.
+pub(crate) struct PanicSignature {
+    pub(crate) location: String,
+    pub(crate) message: String,
+}
+
+impl PartialEq for PanicSignature {
+    fn eq(&self, other: &Self) -> bool {
+        self.location == other.location
+            && collapse_digits(&self.message) == collapse_digits(&other.message)
+    }
+}
+
+impl Trident {
+    fn reset_for_minimization(&mut self, seed: Seed) {
+        self.next_iteration();
+        self.set_master_seed_for_debug(seed);
+        self.rng.set_input(Vec::new());
+    }
+}
+
+/// Minimizes the flow calls of a failing iteration
+///
+/// The iteration is executed once while recording the RNG values consumed by every
+/// flow call. Subsequences of the flow calls (each flow call getting its original values)
+/// are then executed using delta debugging until no flow call can be removed without
+/// losing the original panic. `#[init]` and `#[end]` flows are always kept.
+///
+/// # Arguments
+/// * `failing_iteration` - Seed of the failing iteration or path to its crash artifact
+/// * `flow_calls_per_iteration` - Number of flow calls of the original iteration
+pub(crate) fn minimize<F: FlowExecutor>(failing_iteration: &str, flow_calls_per_iteration: u64) {
+    let mut fuzzer = F::new();
+
+    let (seed, input, swarm, weights) = if Path::new(failing_iteration).is_file() {
+        let artifact = CrashArtifact::load(Path::new(failing_iteration)).unwrap_or_else(|e| {
+            panic!("Failed to load crash artifact {}: {}", failing_iteration, e)
+        });
+        // The `#[init]` snapshot is restored by every execution of the iteration
+        if let Some(init_seed) = Trident::crash_artifact_init_seed(&artifact) {
+            take_init_snapshot(&mut fuzzer, init_seed);
+        }
+        (
+            Trident::crash_artifact_seed(&artifact),
+            Trident::crash_artifact_input(&artifact),
+            artifact.swarm,
+            artifact.weights,
+        )
+    } else {
+        (F::parse_hex_seed(failing_iteration), None, None, None)
+    };
+
+    // Record the original iteration split into flow calls
+    let trident = fuzzer.trident_mut();
+    trident.set_master_seed_for_debug(seed);
+    trident.rng.set_input_and_record(input.unwrap_or_default());
+    if let Some(flows) = swarm {
+        trident.force_swarm_flows(flows);
+    }
+    if let Some(weights) = weights {
+        trident.force_adaptive_weights(weights);
+    }
+    trident.record_flow_steps();
+    let failure = execute_iteration(&mut fuzzer, flow_calls_per_iteration);
+    let steps = fuzzer.trident_mut().take_flow_steps();
+    fuzzer.trident_mut().rng.take_recorded_input();
+    fuzzer.reset_fuzz_accounts();
+
+    let Some(failure) = failure else {
+        eprintln!(
+            "The iteration with seed {} does not fail, nothing to minimize",
+            hex::encode(seed)
+        );
+        std::process::exit(1);
+    };
+
+    let original_flow_calls = steps.iter().filter(|step| step.is_selected()).count();
+    let selected: Vec<usize> = (0..steps.len())
+        .filter(|index| steps[*index].is_selected())
+        .collect();
+    let mut executions = 0;
+    let kept = ddmin(selected, |kept| {
+        executions += 1;
+        replay_steps(&mut fuzzer, seed, &select_steps(&steps, kept)).as_ref() == Some(&failure)
+    });
+
+    // Execute the minimized sequence once more to collect its trace
+    let minimized = select_steps(&steps, &kept);
+    fuzzer.trident_mut().record_iteration_trace();
+    let final_failure = replay_steps(&mut fuzzer, seed, &minimized).unwrap_or(failure);
+
+    println!(
+        "Minimized the failing iteration from {} to {} flow calls ({} executions):",
+        original_flow_calls,
+        kept.len(),
+        executions
+    );
+    for step in &minimized {
+        println!("  {}", step.flow);
+    }
+    println!(
+        "Assertion failed at {}: {} (seed: {})",
+        final_failure.location,
+        final_failure.message,
+        hex::encode(seed)
+    );
+
+    let trident = fuzzer.trident_mut();
+    let mut artifact = trident.crash_artifact(&final_failure.location, &final_failure.message);
+    artifact.input = None;
+    artifact.steps = Some(minimized.iter().map(CrashFlowStep::from).collect());
+    if let Some(path) = trident.write_crash_artifact(&artifact) {
+        println!("Minimized artifact: {}", path.display());
+    }
+}
+
+/// Delta debugging, removes chunks of `items` while `reproduces` holds for the rest
+///
+/// The chunks get smaller until no single item can be removed. The order of the kept
+/// items is preserved.
+fn ddmin<T: Clone>(mut kept: Vec<T>, mut reproduces: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    if reproduces(&[]) {
+        return Vec::new();
+    }
+
+    let mut granularity = 2;
+    while kept.len() >= 2 {
+        let chunk_size = kept.len().div_ceil(granularity);
+        let mut reduced = false;
+
+        for start in (0..kept.len()).step_by(chunk_size) {
+            let end = (start + chunk_size).min(kept.len());
+            let complement: Vec<T> = kept[..start].iter().chain(&kept[end..]).cloned().collect();
+
+            if reproduces(&complement) {
+                kept = complement;
+                granularity = (granularity - 1).max(2);
+                reduced = true;
+                break;
+            }
+        }
+
+        if !reduced {
+            if granularity >= kept.len() {
+                break;
+            }
+            granularity = (granularity * 2).min(kept.len());
+        }
+    }
+    kept
+}
+
+fn select_steps(steps: &[RecordedStep], kept: &[usize]) -> Vec<RecordedStep> {
+    steps
+        .iter()
+        .enumerate()
+        .filter(|(index, step)| !step.is_selected() || kept.binary_search(index).is_ok())
+        .map(|(_, step)| step.clone())
+        .collect()
+}
+
+fn replay_steps<F: FlowExecutor>(
+    fuzzer: &mut F,
+    seed: Seed,
+    steps: &[RecordedStep],
+) -> Option<PanicSignature> {
+    fuzzer.trident_mut().reset_for_minimization(seed);
+    fuzzer.reset_fuzz_accounts();
+    fuzzer.trident_mut().replay_flow_steps(steps);
+
+    let flow_calls = steps.iter().filter(|step| step.is_selected()).count() as u64;
+    let failure = execute_iteration(fuzzer, flow_calls);
+    // Without the removed flow calls, a kept flow may violate its `requires`, `after`,
+    // `once` or `max_calls` constraint, such sequences cannot occur while fuzzing
+    let diverged = fuzzer.trident_mut().flow_steps_diverged();
+    fuzzer.trident_mut().take_flow_steps();
+    failure.filter(|_| !diverged)
+}
+
+pub(crate) fn execute_iteration<F: FlowExecutor>(
+    fuzzer: &mut F,
+    flow_calls_per_iteration: u64,
+) -> Option<PanicSignature> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let _ = fuzzer.execute_flows(flow_calls_per_iteration);
+    }));
+
+    result.err().map(|panic_err| PanicSignature {
+        location: PANIC_LOCATION.with(|cell| cell.take().unwrap_or_else(|| "unknown".to_string())),
+        message: F::extract_panic_message(&panic_err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(flow: &str, selection: Option<u64>) -> RecordedStep {
+        RecordedStep {
+            flow: flow.to_string(),
+            selection,
+            input: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_ddmin_finds_failure_inducing_items() {
+        let kept = ddmin((0..10).collect(), |items: &[usize]| {
+            items.contains(&3) && items.contains(&7)
+        });
+        assert_eq!(kept, vec![3, 7]);
+    }
+
+    #[test]
+    fn test_ddmin_single_item() {
+        let kept = ddmin((0..16).collect(), |items: &[usize]| items.contains(&11));
+        assert_eq!(kept, vec![11]);
+    }
+
+    #[test]
+    fn test_ddmin_failure_without_items() {
+        let mut executions = 0;
+        let kept = ddmin((0..8).collect(), |_: &[usize]| {
+            executions += 1;
+            true
+        });
+        assert!(kept.is_empty());
+        assert_eq!(executions, 1);
+    }
+
+    #[test]
+    fn test_ddmin_keeps_all_required_items() {
+        let kept = ddmin((0..5).collect(), |items: &[usize]| items.len() == 5);
+        assert_eq!(kept, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_ddmin_preserves_order() {
+        // Reproduces only if 2 is followed by 5 later in the sequence
+        let kept = ddmin(vec![5, 1, 2, 8, 5, 9], |items: &[usize]| {
+            items
+                .iter()
+                .position(|item| *item == 2)
+                .is_some_and(|position| items[position..].contains(&5))
+        });
+        assert_eq!(kept, vec![2, 5]);
+    }
+
+    #[test]
+    fn test_select_steps_keeps_init_and_end() {
+        let steps = vec![
+            step("init", None),
+            step("deposit", Some(0)),
+            step("withdraw", Some(1)),
+            step("deposit", Some(0)),
+            step("end", None),
+        ];
+        let selected = select_steps(&steps, &[2]);
+        let flows: Vec<&str> = selected.iter().map(|step| step.flow.as_str()).collect();
+        assert_eq!(flows, vec!["init", "withdraw", "end"]);
+    }
+}
@@ -1,21 +1,63 @@
+use std::sync::Arc;
+
+use trident_config::fuzz::FuzzProgram;
 use trident_config::TridentConfig;
 use trident_fuzz_metrics::TridentFuzzingData;
 use trident_svm::trident_svm::TridentSVM;
 use trident_svm::types::trident_account::TridentAccountSharedData;
 use trident_svm::types::trident_program::TridentProgram;
 
+use crate::trident::account_state::AccountStateInvariants;
+use crate::trident::account_substitution::SubstitutionState;
+use crate::trident::adaptive::AdaptiveWeights;
+use crate::trident::compute_budget::ComputeUnitBudgets;
+use crate::trident::differential::Differential;
+use crate::trident::feedback::ExecutionFeedback;
+use crate::trident::flow_steps::FlowSteps;
+use crate::trident::invariants::InvariantCheck;
+use crate::trident::lamport_conservation::LamportConservation;
+use crate::trident::replay::Decisions;
+use crate::trident::snapshot::InitSnapshot;
+use crate::trident::stop_signal::StopSignal;
+use crate::trident::swarm::Swarm;
+use crate::trident::trace::IterationTrace;
+use crate::trident::value_bias::ValueBias;
+use crate::trident::watchdog::Watchdog;
 use crate::trident_rng::TridentRng;
 
+mod account_state;
+mod account_substitution;
+mod adaptive;
+pub mod budget;
 mod client;
+mod compute_budget;
+pub mod corpus;
+mod dictionary;
+mod differential;
+mod engine;
+mod feedback;
 pub mod flow_executor;
+mod flow_selection;
+mod flow_steps;
+mod invariants;
+mod lamport_conservation;
+pub mod stop_signal;
 mod system;
+mod trace;
 mod transaction_result;
+mod value_bias;
+mod watchdog;
 
 mod metrics;
+mod minimize;
 mod random;
+mod replay;
 mod seed;
+mod snapshot;
 #[cfg(feature = "stake")]
 mod stake;
+mod swarm;
+mod sync;
 #[cfg(feature = "token")]
 mod token;
 #[cfg(feature = "token")]
@@ -23,6 +65,9 @@ mod token2022;
 #[cfg(feature = "vote")]
 mod vote;
 
+pub use flow_selection::FlowCandidate;
+pub use value_bias::RangeValue;
+
 // Re-export token2022 types
 #[cfg(feature = "token")]
 pub use token2022::AccountExtension;
@@ -41,30 +86,88 @@ pub struct Trident {
     pub(crate) client: TridentSVM,
     pub(crate) fuzzing_data: TridentFuzzingData,
     pub(crate) rng: TridentRng,
+    pub(crate) feedback: ExecutionFeedback,
+    pub(crate) trace: IterationTrace,
+    pub(crate) flow_steps: FlowSteps,
+    pub(crate) decisions: Decisions,
+    pub(crate) adaptive: AdaptiveWeights,
+    pub(crate) watchdog: Watchdog,
+    pub(crate) stop: StopSignal,
+    pub(crate) swarm: Swarm,
+    pub(crate) invariant: Option<InvariantCheck>,
+    pub(crate) snapshot: InitSnapshot,
+    pub(crate) differential: Differential,
+    pub(crate) substitution: SubstitutionState,
+    pub(crate) value_bias: Arc<ValueBias>,
+    pub(crate) compute_units: ComputeUnitBudgets,
+    pub(crate) lamport_conservation: LamportConservation,
+    pub(crate) account_state: AccountStateInvariants,
 }
 
 impl Default for Trident {
     fn default() -> Self {
+        Self::new(&TridentConfig::new())
+    }
+}
+
+impl Trident {
+    /// Creates the fuzzing environment of the given `Trident.toml`, the configuration of
+    /// all checks and value generation is read once here
+    pub(crate) fn new(config: &TridentConfig) -> Self {
+        let programs = config.programs();
+        let client = Self::new_client(config, &programs, false);
+
+        // Differential fuzzing executes the transactions with the candidate builds as well
+        let candidate = programs
+            .iter()
+            .any(|program| program.candidate.is_some())
+            .then(|| Self::new_client(config, &programs, true));
+        let differential = Differential::new(&client, candidate, &programs);
+
         Self {
-            client: Self::new_client(),
+            client,
             fuzzing_data: TridentFuzzingData::default(),
             rng: TridentRng::default(),
+            feedback: ExecutionFeedback::default(),
+            trace: IterationTrace::new(),
+            flow_steps: FlowSteps::default(),
+            decisions: Decisions::default(),
+            adaptive: AdaptiveWeights::default(),
+            watchdog: Watchdog::default(),
+            stop: StopSignal::default(),
+            swarm: Swarm::new(),
+            invariant: None,
+            snapshot: InitSnapshot::new(config),
+            differential,
+            substitution: SubstitutionState::new(config),
+            value_bias: Arc::new(ValueBias::new(config)),
+            compute_units: ComputeUnitBudgets::new(config),
+            lamport_conservation: LamportConservation::new(config),
+            account_state: AccountStateInvariants::new(config),
         }
     }
-}
 
-impl Trident {
-    fn new_client() -> TridentSVM {
-        let config = TridentConfig::new();
+    /// Creates a fuzzing environment without any programs, accounts or checks
+    #[cfg(test)]
+    pub(crate) fn for_tests() -> Self {
+        Self::new(&TridentConfig { fuzz: None })
+    }
+
+    /// Creates the SVM with the programs and accounts of `Trident.toml`, the `candidate`
+    /// SVM of differential fuzzing deploys the candidate builds of the programs
+    fn new_client(config: &TridentConfig, programs: &[FuzzProgram], candidate: bool) -> TridentSVM {
         let program_binaries =
-            config
-                .programs()
+            programs
                 .iter()
                 .fold(Vec::new(), |mut sbf_programs, config_program| {
+                    let data = match &config_program.candidate {
+                        Some(candidate_data) if candidate => candidate_data.clone(),
+                        _ => config_program.data.clone(),
+                    };
                     let target = TridentProgram::new(
                         config_program.address,
                         config_program.upgrade_authority,
-                        config_program.data.clone(),
+                        data,
                     );
 
                     sbf_programs.push(target);
@@ -90,6 +193,11 @@ impl Trident {
         svm_builder.with_sbf_programs(program_binaries);
         svm_builder.with_permanent_accounts(permanent_accounts);
 
+        // Logs are written by the baseline SVM only
+        if candidate {
+            return svm_builder.build();
+        }
+
         if std::env::var("TRIDENT_FUZZ_DEBUG_PATH").is_ok()
             && std::env::var("TRIDENT_FUZZ_DEBUG").is_ok()
         {
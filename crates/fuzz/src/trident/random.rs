@@ -1,9 +1,13 @@
+use std::ops::RangeBounds;
+
 use rand::distributions::uniform::SampleRange;
 use rand::distributions::uniform::SampleUniform;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Keypair;
 
+use crate::trident::value_bias::RangeValue;
 use crate::trident::Trident;
+use crate::trident_rng::TridentRng;
 
 impl Trident {
     /// Generates a random value within the specified range
@@ -27,7 +31,33 @@ impl Trident {
         T: SampleUniform,
         R: SampleRange<T>,
     {
-        self.rng.gen_range(range)
+        self.draw("random_from_range", |rng| rng.gen_range(range))
+    }
+
+    /// Generates a random integer or float within the specified range, biased by
+    /// `[fuzz.values]` in the Trident manifest
+    ///
+    /// Values are sampled uniformly, unless `[fuzz.values]` biases the generation toward
+    /// boundary values (such as the range edges, 0, 1, `MAX` and powers of two) or toward
+    /// dictionary values.
+    ///
+    /// # Arguments
+    /// * `range` - The range to sample from (e.g., 0..10, 0..=9)
+    ///
+    /// # Returns
+    /// A random value of type T within the specified range
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let amount = trident.random_biased_from_range(1..=1_000_000u64);
+    /// ```
+    pub fn random_biased_from_range<T, R>(&mut self, range: R) -> T
+    where
+        T: RangeValue,
+        R: SampleRange<T> + RangeBounds<T>,
+    {
+        let bias = self.value_bias.clone();
+        self.draw("random_biased_from_range", |rng| bias.gen_range(rng, range))
     }
 
     /// Generates a random Solana public key
@@ -38,7 +68,7 @@ impl Trident {
     /// # Returns
     /// A randomly generated Pubkey
     pub fn random_pubkey(&mut self) -> Pubkey {
-        self.rng.gen_pubkey()
+        self.draw("random_pubkey", TridentRng::gen_pubkey)
     }
 
     /// Generates a random string of the specified length
@@ -52,7 +82,7 @@ impl Trident {
     /// # Returns
     /// A random string of the specified length
     pub fn random_string(&mut self, length: usize) -> String {
-        self.rng.gen_string(length)
+        self.draw("random_string", |rng| rng.gen_string(length))
     }
 
     /// Fills a byte slice with random data
@@ -64,7 +94,7 @@ impl Trident {
     /// # Arguments
     /// * `bytes` - A mutable byte slice to fill with random data
     pub fn random_bytes(&mut self, bytes: &mut [u8]) {
-        self.rng.fill_bytes(bytes);
+        self.draw("random_bytes", |rng| rng.fill_bytes(bytes));
     }
 
     /// Generates a random boolean value
@@ -79,7 +109,7 @@ impl Trident {
     /// let random_bool = trident.random_bool();
     /// ```
     pub fn random_bool(&mut self) -> bool {
-        self.rng.gen_bool()
+        self.draw("random_bool", TridentRng::gen_bool)
     }
 
     /// Generates a random Solana keypair
@@ -97,6 +127,6 @@ impl Trident {
     /// let authority = trident.random_keypair();
     /// ```
     pub fn random_keypair(&mut self) -> Keypair {
-        self.rng.gen_keypair()
+        self.draw("random_keypair", TridentRng::gen_keypair)
     }
 }
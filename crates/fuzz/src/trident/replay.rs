@@ -0,0 +1,251 @@
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+
+use trident_fuzz_metrics::ReplayFile;
+use trident_fuzz_metrics::ReplayStep;
+use trident_fuzz_metrics::ReplayValue;
+
+use crate::trident::flow_executor::FlowExecutor;
+use crate::trident::minimize::execute_iteration;
+use crate::trident::Trident;
+use crate::trident_rng::TridentRng;
+
+/// A flow call of the current iteration together with the random values it drew
+pub(crate) struct RecordedFlow {
+    flow: String,
+    selected: bool,
+    values: Vec<(&'static str, Vec<u8>)>,
+}
+
+/// Flow selections and random values of an iteration, recorded or replayed
+///
+/// Decisions are recorded only for a single iteration: the failing iteration executed
+/// once more to write its crash artifact, so every failing iteration gets a replay file
+/// next to it. While replaying, flows are selected by their name and values
+/// are handed out per drawing method, so the replay survives changes of the fuzz test
+/// such as added or reordered flows and additional random values. Values missing in
+/// the replay file are generated from the iteration seed.
+#[derive(Default)]
+pub(crate) enum Decisions {
+    #[default]
+    Off,
+    Record {
+        flows: Vec<RecordedFlow>,
+        selecting: bool,
+    },
+    Replay {
+        plan: VecDeque<ReplayStep>,
+        current: Vec<ReplayValue>,
+        selected: bool,
+    },
+}
+
+impl Trident {
+    /// Draws a random value, recording or replaying it under the name of the drawing method
+    pub(crate) fn draw<T>(
+        &mut self,
+        method: &'static str,
+        draw: impl FnOnce(&mut TridentRng) -> T,
+    ) -> T {
+        match &mut self.decisions {
+            Decisions::Off => draw(&mut self.rng),
+            Decisions::Record { flows, .. } => {
+                let (value, bytes) = self.rng.capture(draw);
+                if let Some(flow) = flows.last_mut() {
+                    flow.values.push((method, bytes));
+                }
+                value
+            }
+            Decisions::Replay { current, .. } => {
+                match current.iter().position(|value| value.method == method) {
+                    Some(position) => {
+                        let value = current.remove(position);
+                        let bytes = hex::decode(&value.bytes).unwrap_or_else(|_| {
+                            panic!("Invalid replay value of {}: {}", method, value.bytes)
+                        });
+                        self.rng.replay(bytes, draw)
+                    }
+                    None => draw(&mut self.rng),
+                }
+            }
+        }
+    }
+
+    /// Marks the start of a flow (called from `_record_flow`)
+    pub(crate) fn begin_flow_decisions(&mut self, flow_name: &str) {
+        match &mut self.decisions {
+            Decisions::Off => {}
+            Decisions::Record { flows, selecting } => flows.push(RecordedFlow {
+                flow: flow_name.to_string(),
+                selected: std::mem::take(selecting),
+                values: Vec::new(),
+            }),
+            Decisions::Replay {
+                plan,
+                current,
+                selected,
+            } => {
+                // Values of selected flows are taken by the flow selection
+                if std::mem::take(selected) {
+                    return;
+                }
+                *current = match plan.front() {
+                    Some(step) if !step.selected && step.flow == flow_name => {
+                        plan.pop_front().map(|step| step.values).unwrap_or_default()
+                    }
+                    _ => Vec::new(),
+                };
+            }
+        }
+    }
+
+    /// Selects the next flow of the replay file (replay only)
+    ///
+    /// Recorded flows which no longer exist in the fuzz test are skipped.
+    ///
+    /// # Returns
+    /// Name of the flow to execute, or `None` if the flow should be selected randomly
+    pub(crate) fn is_replaying_decisions(&self) -> bool {
+        matches!(self.decisions, Decisions::Replay { .. })
+    }
+
+    pub(crate) fn replay_flow_selection(
+        &mut self,
+        exists: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        match &mut self.decisions {
+            Decisions::Off => None,
+            Decisions::Record { selecting, .. } => {
+                *selecting = true;
+                None
+            }
+            Decisions::Replay {
+                plan,
+                current,
+                selected,
+            } => {
+                while let Some(step) = plan.pop_front() {
+                    if step.selected && exists(&step.flow) {
+                        *current = step.values;
+                        *selected = true;
+                        return Some(step.flow);
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// Random values drawn by the current flow so far (recording only)
+    pub(crate) fn flow_decisions(&self) -> Vec<(&'static str, Vec<u8>)> {
+        match &self.decisions {
+            Decisions::Record { flows, .. } => flows
+                .last()
+                .map(|flow| flow.values.clone())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Sets the random values of the current flow, used when its execution was skipped
+    pub(crate) fn set_flow_decisions(&mut self, values: Vec<(&'static str, Vec<u8>)>) {
+        if let Decisions::Record { flows, .. } = &mut self.decisions {
+            if let Some(flow) = flows.last_mut() {
+                flow.values = values;
+            }
+        }
+    }
+
+    /// Starts recording the decisions of the current iteration
+    pub(crate) fn record_decisions(&mut self) {
+        self.decisions = Decisions::Record {
+            flows: Vec::new(),
+            selecting: false,
+        };
+    }
+
+    pub(crate) fn is_recording_decisions(&self) -> bool {
+        matches!(self.decisions, Decisions::Record { .. })
+    }
+
+    /// Stops recording or replaying decisions at the end of the iteration
+    pub(crate) fn clear_decisions(&mut self) {
+        self.decisions = Decisions::Off;
+    }
+
+    /// Writes a replay file of the current iteration next to its crash artifact
+    ///
+    /// # Returns
+    /// Path of the replay file, or `None` if recording is disabled or the write failed
+    pub(crate) fn save_replay_file(&mut self, location: &str, message: &str) -> Option<PathBuf> {
+        let Decisions::Record { flows, .. } = &mut self.decisions else {
+            return None;
+        };
+        let replay_file = ReplayFile {
+            iteration_seed: hex::encode(self.rng.get_seed()),
+            location: location.to_string(),
+            message: message.to_string(),
+            steps: flows
+                .drain(..)
+                .map(|flow| ReplayStep {
+                    flow: flow.flow,
+                    selected: flow.selected,
+                    values: flow
+                        .values
+                        .into_iter()
+                        .map(|(method, bytes)| ReplayValue {
+                            method: method.to_string(),
+                            bytes: hex::encode(bytes),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        };
+
+        let directory = self.trace.crashes_directory()?;
+        match replay_file.save(directory) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                eprintln!("Warning: Failed to save replay file: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Executes the iteration recorded in a replay file
+///
+/// # Arguments
+/// * `path` - Path of the replay file
+pub(crate) fn replay<F: FlowExecutor>(path: &str) {
+    let replay_file = ReplayFile::load(Path::new(path))
+        .unwrap_or_else(|e| panic!("Failed to load replay file {}: {}", path, e));
+    let seed = F::parse_hex_seed(&replay_file.iteration_seed);
+    let flow_calls = replay_file.flow_calls();
+
+    println!("Replaying {} ({} flow calls):", path, flow_calls);
+    for step in &replay_file.steps {
+        println!("  {}", step.flow);
+    }
+
+    let mut fuzzer = F::new();
+    let trident = fuzzer.trident_mut();
+    trident.set_master_seed_for_debug(seed);
+    trident.decisions = Decisions::Replay {
+        plan: replay_file.steps.into(),
+        current: Vec::new(),
+        selected: false,
+    };
+
+    match execute_iteration(&mut fuzzer, flow_calls) {
+        Some(failure) => println!(
+            "Assertion failed at {}: {} (seed: {})",
+            failure.location, failure.message, replay_file.iteration_seed
+        ),
+        None => println!(
+            "The replayed iteration finished without a failure (recorded failure at {}: {})",
+            replay_file.location, replay_file.message
+        ),
+    }
+}
@@ -1,6 +1,7 @@
 use trident_fuzz_metrics::TridentFuzzingData;
 
 use trident_fuzz_metrics::types::Seed;
+use trident_svm::trident_svm::TridentSVM;
 
 use crate::trident::Trident;
 
@@ -12,12 +13,26 @@ impl Trident {
 
     pub(crate) fn set_master_seed_and_thread_id(&mut self, seed: Seed, thread_id: usize) {
         self.rng.set_master_seed_and_thread_id(seed, thread_id);
+        self.set_trace_thread_id(thread_id);
         self.fuzzing_data.add_master_seed(&hex::encode(seed));
     }
 
     pub(crate) fn next_iteration(&mut self) {
-        self.client.clear_accounts();
         self.rng.rotate_seed();
+        self.finish_adaptive_flow_call();
+        self.learn_adaptive_weights();
+        self.reset_iteration();
+    }
+
+    /// Clears the state of the current iteration, keeping its seed
+    pub(crate) fn reset_iteration(&mut self) {
+        self.client.clear_accounts();
+        self.mirror_to_candidate(TridentSVM::clear_accounts);
+        self.clear_iteration_trace();
+        self.clear_decisions();
+        self.clear_swarm_flows();
+        self.invariant = None;
+        self.set_stored_addresses(Vec::new());
     }
 
     pub(crate) fn get_fuzzing_data(&self) -> TridentFuzzingData {
@@ -0,0 +1,249 @@
+use std::any::Any;
+use std::collections::HashSet;
+
+use solana_sdk::account::AccountSharedData;
+use solana_sdk::account::ReadableAccount;
+use solana_sdk::clock::Clock;
+use solana_sdk::epoch_rewards::EpochRewards;
+use solana_sdk::epoch_schedule::EpochSchedule;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::slot_hashes::SlotHashes;
+use solana_sdk::stake_history::StakeHistory;
+use trident_config::TridentConfig;
+use trident_fuzz_metrics::types::Seed;
+use trident_svm::trident_svm::TridentSVM;
+
+use crate::trident::flow_executor::FlowExecutor;
+use crate::trident::minimize::execute_iteration;
+use crate::trident::Trident;
+
+/// State of the SVM and of the fuzz accounts after the `#[init]` method,
+/// enabled by `#[init(snapshot)]`
+///
+/// The first iteration of a fuzzing thread executes the `#[init]` method and remembers
+/// every account written by it and the sysvars. Later iterations restore them instead of
+/// executing the `#[init]` method again, the flows still draw fresh random values.
+/// Random values drawn by the `#[init]` method are recorded once and added to the
+/// replay file of every failing iteration, so `trident fuzz replay` reproduces it.
+/// Crash artifacts store the seed of the iteration which took the snapshot, so
+/// debugging and minimizing take the same snapshot, see `take_init_snapshot`.
+#[derive(Default)]
+pub(crate) struct InitSnapshot {
+    enabled: bool,
+    /// The current iteration restored the snapshot instead of executing `#[init]`
+    restored: bool,
+    /// Accounts written while the snapshot is being taken
+    written: Option<HashSet<Pubkey>>,
+    /// Accounts of `[[fuzz.accounts]]`, these are never cleared, restoring them would
+    /// shadow later changes
+    permanent: HashSet<Pubkey>,
+    accounts: Vec<(Pubkey, AccountSharedData)>,
+    sysvars: Option<Sysvars>,
+    fuzz_accounts: Option<Box<dyn Any + Send>>,
+    decisions: Vec<(&'static str, Vec<u8>)>,
+    stored_addresses: Vec<Pubkey>,
+    /// Seed of the iteration which took the snapshot
+    seed: Option<Seed>,
+}
+
+impl InitSnapshot {
+    pub(crate) fn new(config: &TridentConfig) -> Self {
+        Self {
+            permanent: config
+                .accounts()
+                .iter()
+                .map(|account| account.pubkey)
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Sysvars after the `#[init]` method, the SVM keeps sysvars between iterations,
+/// so changes of later iterations (e.g. `warp_to_slot`) are undone by restoring them
+struct Sysvars {
+    clock: Clock,
+    epoch_rewards: EpochRewards,
+    epoch_schedule: EpochSchedule,
+    rent: Rent,
+    slot_hashes: SlotHashes,
+    stake_history: StakeHistory,
+}
+
+impl Sysvars {
+    fn save(client: &TridentSVM) -> Self {
+        Self {
+            clock: client.get_sysvar(),
+            epoch_rewards: client.get_sysvar(),
+            epoch_schedule: client.get_sysvar(),
+            rent: client.get_sysvar(),
+            slot_hashes: client.get_sysvar(),
+            stake_history: client.get_sysvar(),
+        }
+    }
+
+    fn restore(&self, client: &mut TridentSVM) {
+        client.set_sysvar(&self.clock);
+        client.set_sysvar(&self.epoch_rewards);
+        client.set_sysvar(&self.epoch_schedule);
+        client.set_sysvar(&self.rent);
+        client.set_sysvar(&self.slot_hashes);
+        client.set_sysvar(&self.stake_history);
+    }
+}
+
+/// Takes the `#[init]` snapshot the same way as the fuzzing thread which took it,
+/// by executing the iteration with the given seed
+///
+/// # Arguments
+/// * `init_seed` - Seed of the iteration which executed the `#[init]` method
+pub(crate) fn take_init_snapshot<F: FlowExecutor>(fuzzer: &mut F, init_seed: Seed) {
+    let trident = fuzzer.trident_mut();
+    let fuzzing_data = trident.fuzzing_data.clone();
+    trident.enable_init_snapshot();
+    trident.rng.set_master_seed_for_debug(init_seed);
+
+    // Only the `#[init]` method matters, failures of the flows are ignored
+    execute_iteration(fuzzer, 0);
+    fuzzer.trident_mut().next_iteration();
+    fuzzer.reset_fuzz_accounts();
+    fuzzer.trident_mut().fuzzing_data = fuzzing_data;
+}
+
+impl Trident {
+    /// Enables restoring the `#[init]` snapshot, used by the fuzzing loops, debugging
+    /// and minimizing execute the `#[init]` method unless given a crash artifact
+    pub(crate) fn enable_init_snapshot(&mut self) {
+        self.snapshot.enabled = true;
+    }
+
+    /// Prepares the snapshot for executing the failing iteration once more, which executes
+    /// the `#[init]` method again if the failing iteration executed it
+    ///
+    /// # Returns
+    /// Whether the snapshot was enabled, to be passed to `finish_init_snapshot_rerun`
+    pub(crate) fn begin_init_snapshot_rerun(&mut self) -> bool {
+        let enabled = self.snapshot.enabled;
+        self.snapshot.enabled = enabled && self.snapshot.restored;
+        enabled
+    }
+
+    pub(crate) fn finish_init_snapshot_rerun(&mut self, enabled: bool) {
+        self.snapshot.enabled = enabled;
+    }
+
+    /// Seed of the iteration which took the snapshot, if the current iteration restored it
+    pub(crate) fn restored_init_snapshot_seed(&self) -> Option<Seed> {
+        self.snapshot.seed.filter(|_| self.snapshot.restored)
+    }
+
+    /// Restores the state after the `#[init]` method (called by the generated flow executor)
+    ///
+    /// # Returns
+    /// `true` if the snapshot was restored, `false` if the `#[init]` method has to be
+    /// executed, the snapshot is then taken by `_save_init_snapshot`
+    #[doc(hidden)]
+    pub fn _restore_init_snapshot<T: Clone + Send + 'static>(
+        &mut self,
+        init_name: &str,
+        fuzz_accounts: &mut T,
+    ) -> bool {
+        self.snapshot.restored = false;
+        if !self.snapshot.enabled {
+            return false;
+        }
+        let Some(snapshot) = self
+            .snapshot
+            .fuzz_accounts
+            .as_ref()
+            .and_then(|snapshot| snapshot.downcast_ref::<T>())
+        else {
+            self.snapshot.written = Some(HashSet::new());
+            // Values drawn by the `#[init]` method are needed by every replay file
+            if !self.is_recording_decisions() {
+                self.record_decisions();
+            }
+            return false;
+        };
+        *fuzz_accounts = snapshot.clone();
+
+        for (address, account) in &self.snapshot.accounts {
+            self.client.set_account(address, account, false);
+            self.differential.set_account(address, account);
+        }
+        if let Some(sysvars) = self.snapshot.sysvars.take() {
+            sysvars.restore(&mut self.client);
+            self.mirror_to_candidate(|candidate| sysvars.restore(candidate));
+            self.snapshot.sysvars = Some(sysvars);
+        }
+        self._record_flow(init_name);
+        self.set_flow_decisions(self.snapshot.decisions.clone());
+        self.set_stored_addresses(self.snapshot.stored_addresses.clone());
+        self.snapshot.restored = true;
+        true
+    }
+
+    /// Takes the snapshot after the `#[init]` method (called by the generated flow executor)
+    #[doc(hidden)]
+    pub fn _save_init_snapshot<T: Clone + Send + 'static>(&mut self, fuzz_accounts: &T) {
+        let Some(written) = self.snapshot.written.take() else {
+            return;
+        };
+        self.snapshot.accounts = written
+            .into_iter()
+            .filter(|address| !self.snapshot.permanent.contains(address))
+            .filter_map(|address| {
+                let account = self.client.get_account(&address)?;
+                // Programs are not cleared between iterations either, sysvars are saved below
+                (!account.executable() && account.owner() != &solana_sdk::sysvar::id())
+                    .then_some((address, account))
+            })
+            .collect();
+        self.snapshot.sysvars = Some(Sysvars::save(&self.client));
+        self.snapshot.fuzz_accounts = Some(Box::new(fuzz_accounts.clone()));
+        self.snapshot.decisions = self.flow_decisions();
+        self.snapshot.stored_addresses = self.stored_addresses().to_vec();
+        self.snapshot.seed = Some(self.rng.get_seed());
+    }
+
+    /// Remembers accounts written while the snapshot is being taken
+    pub(crate) fn track_snapshot_writes(&mut self, addresses: impl IntoIterator<Item = Pubkey>) {
+        if let Some(written) = self.snapshot.written.as_mut() {
+            written.extend(addresses);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restores_accounts_and_sysvars() {
+        let mut trident = Trident::for_tests();
+        trident.enable_init_snapshot();
+        let address = Pubkey::new_unique();
+        let mut fuzz_accounts = 1_u8;
+
+        // The first iteration executes the `#[init]` method and takes the snapshot
+        assert!(!trident._restore_init_snapshot("init", &mut fuzz_accounts));
+        trident.warp_to_slot(100);
+        trident.set_account_custom(
+            &address,
+            &AccountSharedData::new(10, 0, &Pubkey::new_unique()),
+        );
+        trident._save_init_snapshot(&fuzz_accounts);
+
+        // A later iteration changes the clock and starts without the account
+        trident.reset_iteration();
+        trident.warp_to_slot(5);
+        fuzz_accounts = 2;
+        assert_eq!(trident.get_account(&address).lamports(), 0);
+
+        assert!(trident._restore_init_snapshot("init", &mut fuzz_accounts));
+        assert_eq!(fuzz_accounts, 1);
+        assert_eq!(trident.get_account(&address).lamports(), 10);
+        assert_eq!(trident.get_sysvar::<Clock>().slot, 100);
+    }
+}
@@ -0,0 +1,203 @@
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use crate::trident::Trident;
+
+/// Why the fuzzing session was stopped before its budget was exhausted
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StopReason {
+    /// A failure occurred in fail-fast mode
+    FailFast,
+    /// An iteration timed out and `exit_on_timeout` is enabled
+    Timeout,
+}
+
+/// Stop request shared by all fuzzers of the session
+///
+/// Fuzzers check the signal between iterations and before every flow call, so the
+/// iterations they are running skip their remaining flow calls. Failing iterations
+/// executed once more to write their crash artifacts are finished, and the fuzzing
+/// data of all fuzzers are reported with the rest of the session. The first reason
+/// is kept.
+#[derive(Clone, Default)]
+pub struct StopSignal(Arc<OnceLock<StopReason>>);
+
+impl StopSignal {
+    pub(crate) fn stop(&self, reason: StopReason) {
+        let _ = self.0.set(reason);
+    }
+
+    pub(crate) fn reason(&self) -> Option<StopReason> {
+        self.0.get().copied()
+    }
+
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.0.get().is_some()
+    }
+}
+
+impl Trident {
+    /// Shares the stop signal of the session with this fuzzer, must be set before
+    /// the watchdog is enabled
+    pub(crate) fn set_stop_signal(&mut self, stop: StopSignal) {
+        self.stop = stop;
+    }
+
+    pub(crate) fn stop_signal(&self) -> &StopSignal {
+        &self.stop
+    }
+
+    /// Whether the remaining flow calls of the iteration are skipped because the session
+    /// was stopped, recorded iterations are finished so their crash artifacts reproduce
+    /// the failure
+    pub(crate) fn skips_remaining_flows(&self) -> bool {
+        self.stop.is_stopped() && !self.trace.is_enabled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use super::*;
+    use crate::error::FuzzingError;
+    use crate::trident::budget::FuzzingBudget;
+    use crate::trident::flow_executor::FlowExecutor;
+    use crate::trident::FlowCandidate;
+
+    const FLOW_CALLS: u64 = 100;
+
+    static WAITING_STARTED: AtomicBool = AtomicBool::new(false);
+    static FAILING_CALLS: AtomicU64 = AtomicU64::new(0);
+    static WAITING_CALLS: AtomicU64 = AtomicU64::new(0);
+
+    /// Thread 0 fails in its first flow call once thread 1 is in its first iteration,
+    /// thread 1 waits in its first flow call until the session is stopped
+    struct FailFastFuzzer {
+        trident: Trident,
+    }
+
+    impl FailFastFuzzer {
+        fn flow(&mut self) {
+            if self.trident.trace.thread_id() == Some(0) {
+                FAILING_CALLS.fetch_add(1, Ordering::Relaxed);
+                wait_until(|| WAITING_STARTED.load(Ordering::Relaxed));
+                panic!("failure of thread 0");
+            }
+            WAITING_CALLS.fetch_add(1, Ordering::Relaxed);
+            WAITING_STARTED.store(true, Ordering::Relaxed);
+            wait_until(|| self.trident.stop_signal().is_stopped());
+        }
+    }
+
+    impl FlowExecutor for FailFastFuzzer {
+        fn new() -> Self {
+            Self {
+                trident: Trident::for_tests(),
+            }
+        }
+
+        fn execute_flows(&mut self, flow_calls_per_iteration: u64) -> Result<(), FuzzingError> {
+            for _ in 0..flow_calls_per_iteration {
+                let flows = [FlowCandidate {
+                    name: "flow",
+                    weight: 1,
+                    enabled: true,
+                    after: &[],
+                    max_calls: None,
+                }];
+                if let Some(0) = self.trident._select_flow(&flows) {
+                    self.trident._record_flow("flow");
+                    self.flow();
+                }
+            }
+            Ok(())
+        }
+
+        fn trident_mut(&mut self) -> &mut Trident {
+            &mut self.trident
+        }
+
+        fn reset_fuzz_accounts(&mut self) {}
+
+        fn handle_llvm_coverage(&mut self, _current_iteration: u64) {}
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        let started = Instant::now();
+        while !condition() {
+            assert!(
+                started.elapsed() < Duration::from_secs(10),
+                "wait timed out"
+            );
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_stop_signal_is_shared_by_clones() {
+        let stop = StopSignal::default();
+        let fuzzer = stop.clone();
+        assert!(!fuzzer.is_stopped());
+
+        stop.stop(StopReason::Timeout);
+        stop.stop(StopReason::FailFast);
+        assert!(fuzzer.is_stopped());
+        assert_eq!(fuzzer.reason(), Some(StopReason::Timeout));
+    }
+
+    #[test]
+    fn test_fail_fast_stops_other_threads_within_iteration() {
+        std::env::set_var("TRIDENT_FUZZ_FAIL_FAST", "1");
+        let stop = StopSignal::default();
+        let panic_occurred = Arc::new(AtomicBool::new(false));
+        let completed_iterations = Arc::new(AtomicU64::new(0));
+
+        let threads: Vec<_> = (0..2)
+            .map(|thread_id| {
+                let stop = stop.clone();
+                let panic_occurred = panic_occurred.clone();
+                let completed_iterations = completed_iterations.clone();
+                std::thread::spawn(move || {
+                    FailFastFuzzer::run_thread_workload(
+                        [0; 32],
+                        thread_id,
+                        FuzzingBudget::Iterations(1000),
+                        FLOW_CALLS,
+                        indicatif::ProgressBar::hidden(),
+                        panic_occurred,
+                        stop,
+                        completed_iterations,
+                        None,
+                    )
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(stop.reason(), Some(StopReason::FailFast));
+        assert_eq!(FAILING_CALLS.load(Ordering::Relaxed), 1);
+        // The iteration of the waiting thread skipped its remaining flow calls and the
+        // thread did not start another iteration
+        assert_eq!(WAITING_CALLS.load(Ordering::Relaxed), 1);
+        assert_eq!(completed_iterations.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_recorded_iterations_are_not_stopped() {
+        let mut trident = Trident::for_tests();
+        assert!(!trident.skips_remaining_flows());
+
+        trident.stop_signal().stop(StopReason::FailFast);
+        assert!(trident.skips_remaining_flows());
+
+        trident.record_iteration_trace();
+        assert!(!trident.skips_remaining_flows());
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use rand::rngs::SmallRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::trident::FlowCandidate;
+use crate::trident::Trident;
+
+pub(crate) const ENV_FUZZ_SWARM: &str = "TRIDENT_FUZZ_SWARM";
+
+/// Swarm testing of the flow selection, enabled by `[fuzz.flows] swarm`
+///
+/// Every iteration disables a random subset of the flows and selects only from the
+/// rest, so long runs of a few specific flows become likely even with many flows.
+/// The subset is derived from the iteration seed by its own RNG, so it does not shift
+/// the random values of the iteration and the seed reproduces the same subset.
+/// Replayed iterations select the recorded flows, regardless of the subset.
+#[derive(Default)]
+pub(crate) struct Swarm {
+    enabled: bool,
+    /// Flows enabled in the current iteration, chosen at the first flow selection
+    flows: Option<Vec<String>>,
+    /// Flows enabled in the debugged iteration, loaded from its crash artifact
+    forced: Option<Vec<String>>,
+}
+
+impl Swarm {
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: std::env::var(ENV_FUZZ_SWARM).is_ok(),
+            ..Default::default()
+        }
+    }
+}
+
+impl Trident {
+    /// Disables swarm mode, used when the iteration is driven by an external input
+    pub(crate) fn disable_swarm(&mut self) {
+        self.swarm.enabled = false;
+    }
+
+    /// Enables exactly the given flows in the next iteration
+    pub(crate) fn force_swarm_flows(&mut self, flows: Vec<String>) {
+        self.swarm.forced = Some(flows);
+    }
+
+    /// Flows enabled in the current iteration, `None` if swarm mode is disabled
+    pub(crate) fn swarm_flows(&self) -> Option<Vec<String>> {
+        self.swarm.flows.clone()
+    }
+
+    pub(crate) fn clear_swarm_flows(&mut self) {
+        self.swarm.flows = None;
+    }
+
+    /// Chooses the flows of the current iteration, if not chosen yet
+    ///
+    /// Every flow is kept with a probability of one half, at least one flow is always kept.
+    pub(crate) fn choose_swarm_flows(&mut self, flows: &[FlowCandidate]) {
+        if self.swarm.flows.is_some() {
+            return;
+        }
+        if let Some(forced) = self.swarm.forced.take() {
+            self.swarm.flows = Some(forced);
+            return;
+        }
+        if !self.swarm.enabled || flows.is_empty() {
+            return;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        ("swarm", self.rng.get_seed()).hash(&mut hasher);
+        let mut rng = SmallRng::seed_from_u64(hasher.finish());
+
+        let mut enabled: Vec<String> = flows
+            .iter()
+            .filter(|_| rng.gen_bool(0.5))
+            .map(|flow| flow.name.to_string())
+            .collect();
+        if enabled.is_empty() {
+            enabled.push(flows[rng.gen_range(0..flows.len())].name.to_string());
+        }
+        self.swarm.flows = Some(enabled);
+    }
+
+    /// Returns `false` if swarm mode disabled the flow in the current iteration
+    pub(crate) fn is_swarm_enabled(&self, flow: &str) -> bool {
+        self.swarm
+            .flows
+            .as_ref()
+            .is_none_or(|flows| flows.iter().any(|enabled| enabled == flow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEEDS: u8 = 32;
+
+    const FLOWS: [FlowCandidate; 4] = [
+        flow("deposit"),
+        flow("withdraw"),
+        flow("transfer"),
+        flow("close"),
+    ];
+
+    const fn flow(name: &'static str) -> FlowCandidate {
+        FlowCandidate {
+            name,
+            weight: 1,
+            enabled: true,
+            after: &[],
+            max_calls: None,
+        }
+    }
+
+    fn swarm_flows_of_seed(seed: u8) -> Vec<String> {
+        let mut trident = Trident::for_tests();
+        trident.swarm.enabled = true;
+        trident.set_master_seed_for_debug([seed; 32]);
+        trident.choose_swarm_flows(&FLOWS);
+        trident.swarm_flows().unwrap()
+    }
+
+    #[test]
+    fn test_subset_is_deterministic_per_seed() {
+        let mut subsets = Vec::new();
+        for seed in 0..SEEDS {
+            let subset = swarm_flows_of_seed(seed);
+            assert!(!subset.is_empty());
+            assert_eq!(subset, swarm_flows_of_seed(seed));
+            subsets.push(subset);
+        }
+        // Different seeds enable different subsets
+        subsets.sort();
+        subsets.dedup();
+        assert!(subsets.len() > 1);
+    }
+
+    #[test]
+    fn test_disabled_flows_are_not_selected() {
+        for seed in 0..SEEDS {
+            let mut trident = Trident::for_tests();
+            trident.swarm.enabled = true;
+            trident.set_master_seed_for_debug([seed; 32]);
+            let subset = swarm_flows_of_seed(seed);
+            for _ in 0..10 {
+                let selected = FLOWS[trident._select_flow(&FLOWS).unwrap()].name;
+                trident._record_flow(selected);
+                assert!(subset.iter().any(|flow| flow == selected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_forced_flows() {
+        let mut trident = Trident::for_tests();
+        trident.force_swarm_flows(vec!["close".to_string()]);
+        trident.choose_swarm_flows(&FLOWS);
+
+        assert!(trident.is_swarm_enabled("close"));
+        assert!(!trident.is_swarm_enabled("deposit"));
+    }
+
+    #[test]
+    fn test_without_swarm_mode() {
+        let mut trident = Trident::for_tests();
+        trident.swarm.enabled = false;
+        trident.choose_swarm_flows(&FLOWS);
+
+        assert_eq!(trident.swarm_flows(), None);
+        assert!(FLOWS.iter().all(|flow| trident.is_swarm_enabled(flow.name)));
+    }
+}
@@ -0,0 +1,206 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use sha2::Digest;
+use sha2::Sha256;
+use trident_fuzz_metrics::types::Seed;
+use trident_fuzz_metrics::TridentFuzzingData;
+
+pub(crate) const ENV_FUZZ_SYNC_DIR: &str = "TRIDENT_FUZZ_SYNC_DIR";
+pub(crate) const ENV_FUZZ_WORKER: &str = "TRIDENT_FUZZ_WORKER";
+pub(crate) const ENV_FUZZ_WORKERS: &str = "TRIDENT_FUZZ_WORKERS";
+pub(crate) const ENV_FUZZ_MERGE: &str = "TRIDENT_FUZZ_MERGE";
+
+/// How often workers import the corpus inputs found by other workers
+pub(crate) const CORPUS_SYNC_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Directory shared by the fuzzing processes of a distributed campaign
+///
+/// Started by `trident fuzz run --workers N` (worker processes of one machine) or
+/// `--sync-dir` (joining a campaign over a shared filesystem). Workers share crash
+/// artifacts and the corpus of the execution feedback mode through the directory, and
+/// save their fuzzing data to `workers/`, where it is merged once the workers finish.
+pub(crate) struct SyncDir {
+    directory: PathBuf,
+    /// Identifier of the worker, unique across the machines of the campaign
+    worker_id: String,
+    /// Index of the worker among the workers started on this machine
+    worker: usize,
+    /// Number of workers started on this machine, sharing its threads and iterations
+    workers: usize,
+}
+
+impl SyncDir {
+    pub(crate) fn from_env() -> Option<Self> {
+        let directory = std::env::var(ENV_FUZZ_SYNC_DIR).ok()?;
+        let parse = |name: &str, default: usize| {
+            std::env::var(name).map_or(default, |value| {
+                value
+                    .parse::<usize>()
+                    .unwrap_or_else(|_| panic!("Invalid {}: {}", name, value))
+            })
+        };
+        let worker = parse(ENV_FUZZ_WORKER, 0);
+        Some(Self {
+            directory: PathBuf::from(directory),
+            worker_id: worker_id(worker),
+            worker,
+            workers: parse(ENV_FUZZ_WORKERS, 1).max(1),
+        })
+    }
+
+    pub(crate) fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Master seed of this worker, derived from the master seed of the campaign and the
+    /// worker index, so workers of the campaign fuzz different iterations and the
+    /// campaign seed reproduces all of them
+    pub(crate) fn worker_master_seed(&self, master_seed: Seed) -> Seed {
+        worker_master_seed(master_seed, self.worker)
+    }
+
+    fn workers_directory(&self) -> PathBuf {
+        self.directory.join("workers")
+    }
+
+    /// Saves the fuzzing data of this worker, named by its worker id so workers
+    /// of different machines do not overwrite each other
+    pub(crate) fn save_worker_data(&self, fuzzing_data: &TridentFuzzingData) {
+        let directory = self.workers_directory();
+        let path = directory.join(format!("worker_{}.json", self.worker_id));
+        if let Err(e) =
+            fs::create_dir_all(&directory).and_then(|_| fuzzing_data.save_worker_data(&path))
+        {
+            eprintln!(
+                "Warning: Failed to save worker data {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+
+    /// Merges the fuzzing data of all workers which finished so far, keeping the master
+    /// seed of the campaign
+    ///
+    /// # Returns
+    /// The merged fuzzing data with the number of merged workers
+    pub(crate) fn merge_worker_data(&self) -> (TridentFuzzingData, usize) {
+        let mut merged = 0;
+
+        let mut paths = fs::read_dir(self.workers_directory())
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        paths.sort();
+
+        let mut worker_data = Vec::new();
+        for path in paths {
+            match TridentFuzzingData::load_worker_data(&path) {
+                Ok(data) => {
+                    worker_data.push(data);
+                    merged += 1;
+                }
+                Err(e) => eprintln!(
+                    "Warning: Failed to load worker data {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        (merge_worker_data(worker_data), merged)
+    }
+
+    pub(crate) fn display(&self) -> std::path::Display<'_> {
+        self.directory.display()
+    }
+}
+
+fn worker_master_seed(master_seed: Seed, worker: usize) -> Seed {
+    let mut hasher = Sha256::new();
+    hasher.update(master_seed);
+    hasher.update((worker as u64).to_le_bytes());
+    hasher.finalize().into()
+}
+
+/// Merges the fuzzing data of workers, the master seed is taken from the first worker
+/// which saved one, all workers of a campaign save the master seed of the campaign
+fn merge_worker_data(worker_data: Vec<TridentFuzzingData>) -> TridentFuzzingData {
+    let mut fuzzing_data = TridentFuzzingData::default();
+    for data in worker_data {
+        if fuzzing_data.get_master_seed().is_none() {
+            if let Some(master_seed) = data.get_master_seed() {
+                fuzzing_data.add_master_seed(master_seed);
+            }
+        }
+        fuzzing_data._merge(data);
+    }
+    fuzzing_data
+}
+
+/// Identifier of the worker made of the host name, the process id and the index of the
+/// worker among the workers started on this machine
+fn worker_id(worker: usize) -> String {
+    let host = std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "localhost".to_string());
+    // The id is a part of file names
+    let host: String = host
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}-{}-{}", host, std::process::id(), worker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worker_master_seed_depends_on_campaign_seed_and_index() {
+        let campaign_seed = [1; 32];
+
+        assert_eq!(
+            worker_master_seed(campaign_seed, 0),
+            worker_master_seed(campaign_seed, 0)
+        );
+        assert_ne!(
+            worker_master_seed(campaign_seed, 0),
+            worker_master_seed(campaign_seed, 1)
+        );
+        assert_ne!(
+            worker_master_seed(campaign_seed, 0),
+            worker_master_seed([2; 32], 0)
+        );
+    }
+
+    #[test]
+    fn test_merge_worker_data_keeps_master_seed() {
+        let campaign_seed = [3; 32];
+        let worker_data = vec![
+            TridentFuzzingData::default(),
+            TridentFuzzingData::with_master_seed(campaign_seed),
+            TridentFuzzingData::with_master_seed(campaign_seed),
+        ];
+
+        let merged = merge_worker_data(worker_data);
+        assert_eq!(
+            merged.get_master_seed(),
+            Some(hex::encode(campaign_seed).as_str())
+        );
+    }
+}
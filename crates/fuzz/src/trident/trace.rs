@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use trident_fuzz_metrics::types::Seed;
+use trident_fuzz_metrics::CrashArtifact;
+use trident_fuzz_metrics::CrashTransaction;
+use trident_fuzz_metrics::TridentFuzzingData;
+
+use crate::trident::flow_executor::FlowExecutor;
+use crate::trident::flow_steps::RecordedStep;
+use crate::trident::minimize::execute_iteration;
+use crate::trident::transaction_result::TransactionResult;
+use crate::trident::Trident;
+
+/// Environment variable with the directory for crash artifacts, set by the Trident CLI
+pub(crate) const ENV_FUZZ_CRASHES: &str = "TRIDENT_FUZZ_CRASHES";
+
+/// Record of the current iteration used to write crash artifacts
+///
+/// Flows and transactions are only recorded while a failing iteration is executed once
+/// more to write its crash artifact (see `save_failure_trace`), otherwise the trace stays
+/// empty. Flow calls are always counted, per flow as well.
+#[derive(Default)]
+pub(crate) struct IterationTrace {
+    crashes_directory: Option<PathBuf>,
+    recording: bool,
+    thread_id: Option<usize>,
+    flow_calls: u64,
+    flow_counts: HashMap<String, u32>,
+    flows: Vec<String>,
+    transactions: Vec<CrashTransaction>,
+}
+
+impl IterationTrace {
+    pub(crate) fn new() -> Self {
+        Self {
+            crashes_directory: std::env::var(ENV_FUZZ_CRASHES).ok().map(PathBuf::from),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn crashes_directory(&self) -> Option<&Path> {
+        self.crashes_directory.as_deref()
+    }
+
+    pub(crate) fn thread_id(&self) -> Option<usize> {
+        self.thread_id
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.recording
+    }
+}
+
+/// State of the fuzzing thread set aside while a failing iteration is executed once more
+struct FailureTrace {
+    fuzzing_data: TridentFuzzingData,
+    feedback: bool,
+    init_snapshot: bool,
+}
+
+/// Executes the failing iteration once more with its trace recorded and writes its
+/// crash artifact
+///
+/// Iterations are deterministic given their seed and input, so only failing iterations
+/// pay for recording the flows and transaction logs. The metrics, execution features
+/// and learned flow weights are left as they were after the failing iteration.
+///
+/// # Returns
+/// Paths of the crash artifact and of the replay file, `None` if crash artifacts are
+/// disabled or the write failed
+pub(crate) fn save_failure_trace<F: FlowExecutor>(
+    fuzzer: &mut F,
+    flow_calls_per_iteration: u64,
+    location: &str,
+    message: &str,
+) -> (Option<PathBuf>, Option<PathBuf>) {
+    if fuzzer.trident_mut().trace.crashes_directory.is_none() {
+        return (None, None);
+    }
+
+    let failure = fuzzer.trident_mut().begin_failure_trace();
+    fuzzer.reset_fuzz_accounts();
+    fuzzer.trident_mut().start_iteration_watch();
+    execute_iteration(fuzzer, flow_calls_per_iteration);
+    fuzzer.trident_mut().stop_iteration_watch();
+
+    // The failure is reported as it occurred, even if the execution differed
+    let trident = fuzzer.trident_mut();
+    let artifact = trident.save_crash_artifact(location, message);
+    let replay_file = trident.save_replay_file(location, message);
+    trident.finish_failure_trace(failure);
+    (artifact, replay_file)
+}
+
+impl Trident {
+    /// Records the flow about to be executed (called by the generated flow executor)
+    #[doc(hidden)]
+    pub fn _record_flow(&mut self, flow_name: &str) {
+        self.check_iteration_timeout();
+        self.watch_flow(flow_name);
+        self.trace.flow_calls += 1;
+        *self
+            .trace
+            .flow_counts
+            .entry(flow_name.to_string())
+            .or_default() += 1;
+        if self.trace.is_enabled() {
+            self.trace.flows.push(flow_name.to_string());
+        }
+        self.begin_flow_step(flow_name);
+        self.begin_flow_decisions(flow_name);
+        self.begin_adaptive_flow_call(flow_name);
+    }
+
+    pub(crate) fn record_transaction_trace(
+        &mut self,
+        log_as: Option<&str>,
+        result: &TransactionResult,
+    ) {
+        if self.trace.is_enabled() {
+            self.trace.transactions.push(CrashTransaction {
+                name: log_as.map(str::to_string),
+                flow: self.trace.flows.last().cloned(),
+                result: format!("{:?}", result.get_result()),
+                logs: result.log_messages().to_vec(),
+            });
+        }
+    }
+
+    /// Prepares the failing iteration to be executed once more, keeping its seed, input
+    /// and swarm flows
+    fn begin_failure_trace(&mut self) -> FailureTrace {
+        let failure = FailureTrace {
+            fuzzing_data: self.fuzzing_data.clone(),
+            feedback: self.suspend_feedback(),
+            init_snapshot: self.begin_init_snapshot_rerun(),
+        };
+        let seed = self.rng.get_seed();
+        let swarm = self.swarm_flows();
+
+        self.reset_iteration();
+        self.rng.set_master_seed_for_debug(seed);
+        self.rng.rewind_input();
+        if let Some(flows) = swarm {
+            self.force_swarm_flows(flows);
+        }
+        self.freeze_adaptive_weights(true);
+        self.trace.recording = true;
+        self.record_decisions();
+        failure
+    }
+
+    fn finish_failure_trace(&mut self, failure: FailureTrace) {
+        self.trace.recording = false;
+        self.freeze_adaptive_weights(false);
+        self.resume_feedback(failure.feedback);
+        self.finish_init_snapshot_rerun(failure.init_snapshot);
+        self.fuzzing_data = failure.fuzzing_data;
+    }
+
+    /// Records the trace of the following iterations, used by minimization
+    pub(crate) fn record_iteration_trace(&mut self) {
+        self.trace.recording = true;
+    }
+
+    pub(crate) fn set_trace_thread_id(&mut self, thread_id: usize) {
+        self.trace.thread_id = Some(thread_id);
+    }
+
+    /// Number of flow calls executed in the current iteration so far
+    pub(crate) fn iteration_flow_calls(&self) -> u64 {
+        self.trace.flow_calls
+    }
+
+    /// Number of calls of the flow in the current iteration
+    pub(crate) fn iteration_flow_count(&self, flow_name: &str) -> u32 {
+        self.trace
+            .flow_counts
+            .get(flow_name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn clear_iteration_trace(&mut self) {
+        self.trace.flow_calls = 0;
+        self.trace.flow_counts.clear();
+        self.trace.flows.clear();
+        self.trace.transactions.clear();
+    }
+
+    /// Writes a crash artifact of the current iteration
+    ///
+    /// # Returns
+    /// Path of the artifact, or `None` if crash artifacts are disabled or the write failed
+    pub(crate) fn save_crash_artifact(&mut self, location: &str, message: &str) -> Option<PathBuf> {
+        if !self.trace.is_enabled() {
+            return None;
+        }
+        let artifact = self.crash_artifact(location, message);
+        self.write_crash_artifact(&artifact)
+    }
+
+    pub(crate) fn write_crash_artifact(&self, artifact: &CrashArtifact) -> Option<PathBuf> {
+        let directory = self.trace.crashes_directory.as_ref()?;
+        match artifact.save(directory) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                eprintln!("Warning: Failed to save crash artifact: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Creates a crash artifact of the current iteration, taking the recorded trace
+    pub(crate) fn crash_artifact(&mut self, location: &str, message: &str) -> CrashArtifact {
+        CrashArtifact {
+            master_seed: self.fuzzing_data.get_master_seed().map(str::to_string),
+            iteration_seed: hex::encode(self.rng.get_seed()),
+            thread_id: self.trace.thread_id,
+            location: location.to_string(),
+            message: message.to_string(),
+            flows: std::mem::take(&mut self.trace.flows),
+            transactions: std::mem::take(&mut self.trace.transactions),
+            input: self.rng.recorded_input().map(hex::encode),
+            swarm: self.swarm_flows(),
+            weights: self.adaptive_weights(),
+            init_seed: self.restored_init_snapshot_seed().map(hex::encode),
+            steps: None,
+        }
+    }
+
+    /// Replays the iteration of a crash artifact in debug mode, the `#[init]` snapshot
+    /// is taken beforehand by `take_init_snapshot` if the iteration restored it
+    ///
+    /// # Returns
+    /// Seed of the failing iteration
+    pub(crate) fn load_crash_artifact_for_debug(&mut self, artifact: &CrashArtifact) -> Seed {
+        let seed = Self::crash_artifact_seed(artifact);
+        self.set_master_seed_for_debug(seed);
+        if let Some(input) = Self::crash_artifact_input(artifact) {
+            self.rng.set_input(input);
+        }
+        if let Some(flows) = &artifact.swarm {
+            self.force_swarm_flows(flows.clone());
+        }
+        if let Some(weights) = &artifact.weights {
+            self.force_adaptive_weights(weights.clone());
+        }
+        if let Some(steps) = &artifact.steps {
+            let steps: Vec<RecordedStep> = steps.iter().map(RecordedStep::from).collect();
+            self.replay_flow_steps(&steps);
+        }
+        seed
+    }
+
+    pub(crate) fn crash_artifact_input(artifact: &CrashArtifact) -> Option<Vec<u8>> {
+        artifact
+            .input
+            .as_ref()
+            .map(|input| hex::decode(input).unwrap_or_else(|_| panic!("Invalid input: {}", input)))
+    }
+
+    pub(crate) fn crash_artifact_seed(artifact: &CrashArtifact) -> Seed {
+        Self::decode_seed(&artifact.iteration_seed)
+    }
+
+    pub(crate) fn crash_artifact_init_seed(artifact: &CrashArtifact) -> Option<Seed> {
+        artifact.init_seed.as_deref().map(Self::decode_seed)
+    }
+
+    fn decode_seed(seed: &str) -> Seed {
+        let seed_bytes =
+            hex::decode(seed).unwrap_or_else(|_| panic!("Invalid iteration seed: {}", seed));
+        seed_bytes
+            .try_into()
+            .unwrap_or_else(|_| panic!("Iteration seed must be exactly 32 bytes, got: {}", seed))
+    }
+}
@@ -10,6 +10,7 @@ pub struct TransactionResult {
     transaction_result: solana_sdk::transaction::Result<()>,
     transaction_logs: Vec<String>,
     transaction_timestamp: u64,
+    compute_units_consumed: u64,
 }
 
 impl TransactionResult {
@@ -18,15 +19,18 @@ impl TransactionResult {
     /// # Arguments
     /// * `transaction_result` - The result of the transaction execution
     /// * `transaction_logs` - Logs generated during transaction execution
+    /// * `compute_units_consumed` - Compute units consumed by the transaction
     pub(crate) fn new(
         transaction_result: solana_sdk::transaction::Result<()>,
         transaction_logs: Vec<String>,
         transaction_timestamp: u64,
+        compute_units_consumed: u64,
     ) -> Self {
         Self {
             transaction_result,
             transaction_logs,
             transaction_timestamp,
+            compute_units_consumed,
         }
     }
 
@@ -56,6 +60,12 @@ impl TransactionResult {
     pub fn logs(&self) -> String {
         format!("{:#?}", self.transaction_logs)
     }
+
+    /// Returns the transaction log messages (internal use only)
+    pub(crate) fn log_messages(&self) -> &[String] {
+        &self.transaction_logs
+    }
+
     /// Returns the raw transaction result
     ///
     /// Gets the underlying Solana transaction result, which contains
@@ -118,4 +128,23 @@ impl TransactionResult {
     pub fn get_transaction_timestamp(&self) -> u64 {
         self.transaction_timestamp
     }
+
+    /// Returns the compute units consumed by the transaction.
+    ///
+    /// Transactions which were not executed (for example because an account
+    /// could not be loaded) consumed no compute units.
+    ///
+    /// # Returns
+    ///
+    /// Number of consumed compute units
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let result = trident.process_transaction(&instructions, Some("swap"));
+    /// assert!(result.get_compute_units_consumed() < 100_000);
+    /// ```
+    pub fn get_compute_units_consumed(&self) -> u64 {
+        self.compute_units_consumed
+    }
 }
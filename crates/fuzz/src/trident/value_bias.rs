@@ -0,0 +1,302 @@
+use std::collections::BTreeSet;
+use std::ops::Bound;
+use std::ops::RangeBounds;
+
+use rand::distributions::uniform::SampleRange;
+use rand::distributions::uniform::SampleUniform;
+use trident_config::TridentConfig;
+
+use crate::trident::dictionary::extract_dictionary;
+use crate::trident::dictionary::parse_dictionary_value;
+use crate::trident_rng::TridentRng;
+
+/// Value types supported by `Trident::random_biased_from_range`
+///
+/// Provides the boundary values of the type (such as 0, 1, `MAX`, `MAX - 1` and
+/// powers of two) and the conversion of dictionary values, used when the generation
+/// is biased by the `[fuzz.values]` section of the Trident manifest.
+pub trait RangeValue: SampleUniform + PartialOrd + Copy {
+    /// Boundary values of the type and of a range with the given bounds
+    ///
+    /// Values outside of the range are filtered out by the caller.
+    fn boundary_values(start: Bound<&Self>, end: Bound<&Self>) -> impl Iterator<Item = Self>;
+
+    /// Converts a dictionary value, or returns `None` if the type cannot represent it
+    fn from_dictionary(value: i128) -> Option<Self>;
+}
+
+macro_rules! impl_range_value_int {
+    ($($t:ty),*) => {$(
+        impl RangeValue for $t {
+            fn boundary_values(start: Bound<&Self>, end: Bound<&Self>) -> impl Iterator<Item = Self> {
+                let low = match start {
+                    Bound::Included(&low) => [Some(low), low.checked_add(1)],
+                    Bound::Excluded(&low) => [low.checked_add(1), low.checked_add(2)],
+                    Bound::Unbounded => [None, None],
+                };
+                let high = match end {
+                    Bound::Included(&high) => [Some(high), high.checked_sub(1)],
+                    Bound::Excluded(&high) => [high.checked_sub(1), high.checked_sub(2)],
+                    Bound::Unbounded => [None, None],
+                };
+                [
+                    0,
+                    1,
+                    (0 as $t).wrapping_sub(1),
+                    <$t>::MIN,
+                    <$t>::MIN.wrapping_add(1),
+                    <$t>::MAX,
+                    <$t>::MAX - 1,
+                ]
+                .into_iter()
+                .chain((1..<$t>::BITS).flat_map(|shift| {
+                    let power = (1 as $t) << shift;
+                    [power, power.wrapping_sub(1)]
+                }))
+                .chain(low.into_iter().flatten())
+                .chain(high.into_iter().flatten())
+            }
+
+            fn from_dictionary(value: i128) -> Option<Self> {
+                <$t>::try_from(value).ok()
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_range_value_float {
+    ($($t:ty),*) => {$(
+        impl RangeValue for $t {
+            fn boundary_values(start: Bound<&Self>, end: Bound<&Self>) -> impl Iterator<Item = Self> {
+                let low = match start {
+                    Bound::Included(&low) => Some(low),
+                    _ => None,
+                };
+                let high = match end {
+                    Bound::Included(&high) => Some(high),
+                    _ => None,
+                };
+                [
+                    0.0,
+                    1.0,
+                    -1.0,
+                    <$t>::EPSILON,
+                    <$t>::MIN_POSITIVE,
+                    <$t>::MIN,
+                    <$t>::MAX,
+                ]
+                .into_iter()
+                .chain(low)
+                .chain(high)
+            }
+
+            fn from_dictionary(value: i128) -> Option<Self> {
+                Some(value as $t)
+            }
+        }
+    )*};
+}
+
+impl_range_value_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_range_value_float!(f32, f64);
+
+/// Strategy of `Trident::random_biased_from_range`, configured by `[fuzz.values]`
+///
+/// With the given probabilities a value is picked from the boundary values of the
+/// range, or from the dictionary values within the range. Otherwise the value is sampled
+/// uniformly. Without biasing (the default) no additional random values are drawn, so
+/// seeds reproduce the same iterations as with uniform sampling.
+pub(crate) struct ValueBias {
+    boundary_probability: f64,
+    dictionary_probability: f64,
+    /// Sorted dictionary values, so the values within a range form a contiguous slice
+    dictionary: Vec<i128>,
+}
+
+impl ValueBias {
+    pub(crate) fn new(config: &TridentConfig) -> Self {
+        let boundary_probability = Self::probability(config.get_boundary_probability());
+        let dictionary_probability = Self::probability(config.get_dictionary_probability());
+
+        let mut dictionary: BTreeSet<i128> = config
+            .get_dictionary()
+            .iter()
+            .map(|value| {
+                parse_dictionary_value(value)
+                    .unwrap_or_else(|| panic!("Invalid dictionary value: {}", value))
+            })
+            .collect();
+        if config.get_auto_dictionary() {
+            dictionary.extend(extract_dictionary(config));
+        }
+
+        Self {
+            boundary_probability,
+            dictionary_probability,
+            dictionary: dictionary.into_iter().collect(),
+        }
+    }
+
+    fn probability(probability: f64) -> f64 {
+        if !(0.0..=1.0).contains(&probability) {
+            panic!(
+                "Probability of biased values must be between 0 and 1, got {}",
+                probability
+            );
+        }
+        probability
+    }
+
+    pub(crate) fn gen_range<T, R>(&self, rng: &mut TridentRng, range: R) -> T
+    where
+        T: RangeValue,
+        R: SampleRange<T> + RangeBounds<T>,
+    {
+        if self.boundary_probability > 0.0
+            && rng.gen_bool_with_probability(self.boundary_probability)
+        {
+            let values = || {
+                T::boundary_values(range.start_bound(), range.end_bound())
+                    .filter(|value| range.contains(value))
+            };
+            let count = values().count();
+            if count > 0 {
+                if let Some(value) = values().nth(rng.gen_range(0..count)) {
+                    return value;
+                }
+            }
+        }
+
+        if self.dictionary_probability > 0.0
+            && !self.dictionary.is_empty()
+            && rng.gen_bool_with_probability(self.dictionary_probability)
+        {
+            let values = self.dictionary_values(&range);
+            if !values.is_empty() {
+                if let Some(value) = T::from_dictionary(values[rng.gen_range(0..values.len())]) {
+                    return value;
+                }
+            }
+        }
+
+        rng.gen_range(range)
+    }
+
+    /// Dictionary values which the type can represent and which lie within the range
+    ///
+    /// Conversions of dictionary values preserve their order, so the values are found
+    /// by a binary search of the sorted dictionary.
+    fn dictionary_values<T, R>(&self, range: &R) -> &[i128]
+    where
+        T: RangeValue,
+        R: RangeBounds<T>,
+    {
+        // Values the type cannot represent lie below its minimum if negative,
+        // otherwise above its maximum
+        let below = |value: &i128| match T::from_dictionary(*value) {
+            Some(converted) => match range.start_bound() {
+                Bound::Included(start) => converted < *start,
+                Bound::Excluded(start) => converted <= *start,
+                Bound::Unbounded => false,
+            },
+            None => *value < 0,
+        };
+        let above = |value: &i128| match T::from_dictionary(*value) {
+            Some(converted) => match range.end_bound() {
+                Bound::Included(end) => converted > *end,
+                Bound::Excluded(end) => converted >= *end,
+                Bound::Unbounded => false,
+            },
+            None => *value > 0,
+        };
+        let first = self.dictionary.partition_point(below);
+        let last = self.dictionary.partition_point(|value| !above(value));
+        self.dictionary.get(first..last).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value_bias(dictionary: &[i128]) -> ValueBias {
+        ValueBias {
+            boundary_probability: 0.0,
+            dictionary_probability: 1.0,
+            dictionary: dictionary.to_vec(),
+        }
+    }
+
+    fn filtered<T, R>(dictionary: &[i128], range: &R) -> Vec<i128>
+    where
+        T: RangeValue,
+        R: RangeBounds<T>,
+    {
+        dictionary
+            .iter()
+            .copied()
+            .filter(|value| T::from_dictionary(*value).is_some_and(|value| range.contains(&value)))
+            .collect()
+    }
+
+    #[test]
+    fn test_dictionary_values_within_range() {
+        let dictionary = [
+            i128::MIN,
+            -70_000,
+            -129,
+            -128,
+            -1,
+            0,
+            1,
+            41,
+            42,
+            43,
+            255,
+            256,
+            70_000,
+            u64::MAX as i128 + 1,
+        ];
+        let bias = value_bias(&dictionary);
+
+        assert_eq!(
+            bias.dictionary_values::<u8, _>(&(0..=u8::MAX)),
+            [0, 1, 41, 42, 43, 255]
+        );
+        assert_eq!(bias.dictionary_values::<i8, _>(&(-128..0)), [-128, -1]);
+        assert_eq!(bias.dictionary_values::<u32, _>(&(42..256)), [42, 43, 255]);
+        assert_eq!(bias.dictionary_values::<u32, _>(&(50..60)), [] as [i128; 0]);
+        let (start, end) = (10, 0);
+        assert_eq!(
+            bias.dictionary_values::<i64, _>(&(start..end)),
+            [] as [i128; 0]
+        );
+
+        assert_eq!(
+            bias.dictionary_values::<i16, _>(&(-200..=42)),
+            filtered::<i16, _>(&dictionary, &(-200..=42))
+        );
+        assert_eq!(
+            bias.dictionary_values::<u64, _>(&(1..)),
+            filtered::<u64, _>(&dictionary, &(1..))
+        );
+        assert_eq!(
+            bias.dictionary_values::<f64, _>(&(-1.0..100.0)),
+            filtered::<f64, _>(&dictionary, &(-1.0..100.0))
+        );
+    }
+
+    #[test]
+    fn test_boundary_values_of_range() {
+        let values: Vec<u8> = u8::boundary_values(Bound::Excluded(&10), Bound::Included(&100))
+            .filter(|value| (11..=100).contains(value))
+            .collect();
+        assert_eq!(values, [16, 15, 32, 31, 64, 63, 11, 12, 100, 99]);
+
+        let values: Vec<i16> = i16::boundary_values(Bound::Unbounded, Bound::Unbounded).collect();
+        assert!(values.contains(&i16::MIN));
+        assert!(values.contains(&i16::MAX));
+        assert!(values.contains(&-1));
+        assert!(values.contains(&(1 << 14)));
+    }
+}
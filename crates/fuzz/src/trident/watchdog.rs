@@ -0,0 +1,422 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::Weak;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use trident_fuzz_metrics::types::Seed;
+use trident_fuzz_metrics::CrashArtifact;
+use trident_fuzz_metrics::TridentFuzzingData;
+
+use crate::trident::stop_signal::StopReason;
+use crate::trident::stop_signal::StopSignal;
+use crate::trident::Trident;
+
+/// Exit code of a fuzzing session stopped by a timed out iteration
+pub(crate) const TIMEOUT_EXIT_CODE: i32 = 98;
+
+/// How often the watchdog thread checks the running iterations
+const WATCHDOG_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often fuzzers share their fuzzing data with the watchdog thread
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Iterations watched by the watchdog thread, one slot per fuzzer instance
+static WATCHED_ITERATIONS: OnceLock<Mutex<Vec<Weak<WatchedSlot>>>> = OnceLock::new();
+
+type WatchedSlot = Mutex<WatchedFuzzer>;
+
+/// Panic payload of an iteration aborted because it exceeded the iteration timeout
+pub(crate) struct IterationTimeout {
+    /// Flow which was running when the timeout fired, `None` for the `#[init]` method
+    pub(crate) flow: Option<String>,
+    pub(crate) elapsed: Duration,
+}
+
+/// Per-iteration timeout, configured by `[fuzz.budget] iteration_timeout`
+///
+/// Rust code cannot be interrupted from the outside, so an iteration which exceeded the
+/// timeout is aborted with a panic the next time the fuzz test calls into Trident
+/// (processing a transaction or starting a flow). If the iteration does not return
+/// control to Trident within another timeout period (for example a flow looping forever),
+/// the watchdog thread records the timeout finding and writes a crash artifact with the
+/// iteration seed. The hung fuzzer is then abandoned, its last shared fuzzing data are
+/// reported with the other fuzzers, which continue unless `exit_on_timeout` stops the
+/// session. The fuzzing data are shared with the watchdog thread once per
+/// `PUBLISH_INTERVAL`, so the report misses at most the last interval.
+#[derive(Default)]
+pub(crate) struct Watchdog {
+    timeout: Option<Duration>,
+    started: Option<Instant>,
+    flow: Option<String>,
+    published: Option<Instant>,
+    slot: Arc<WatchedSlot>,
+}
+
+/// State of a fuzzer shared with the watchdog thread
+#[derive(Default)]
+struct WatchedFuzzer {
+    iteration: Option<WatchedIteration>,
+    fuzzing_data: Option<TridentFuzzingData>,
+    crashes_directory: Option<PathBuf>,
+    thread_id: Option<usize>,
+    stop: StopSignal,
+    exit_on_timeout: bool,
+    /// Set once the fuzzer is abandoned, until its fuzzing data are taken
+    hung: bool,
+}
+
+struct WatchedIteration {
+    started: Instant,
+    seed: Seed,
+    timeout: Duration,
+    flow: Option<String>,
+    flow_calls: u64,
+    init_seed: Option<Seed>,
+}
+
+/// Result of waiting for a fuzzer thread, see `wait_for_fuzzer`
+pub(crate) enum FuzzerOutcome<T> {
+    Finished(thread::Result<T>),
+    /// The thread hung in an iteration, with the fuzzing data it shared last
+    Hung(TridentFuzzingData),
+}
+
+impl Watchdog {
+    fn start(&mut self, started: Instant) {
+        self.started = Some(started);
+        self.flow = None;
+    }
+
+    fn stop(&mut self) {
+        if self.started.take().is_some() {
+            self.slot.lock().unwrap().iteration = None;
+        }
+    }
+
+    /// Aborts the current iteration with an `IterationTimeout` panic if it exceeded the
+    /// iteration timeout
+    fn check_iteration_timeout(&mut self) {
+        let (Some(timeout), Some(started)) = (self.timeout, self.started) else {
+            return;
+        };
+        let elapsed = started.elapsed();
+        if elapsed > timeout {
+            // The iteration is aborted only once, further checks of the unwinding
+            // iteration must not panic again
+            self.stop();
+            std::panic::panic_any(IterationTimeout {
+                flow: self.flow.take(),
+                elapsed,
+            });
+        }
+    }
+}
+
+impl Trident {
+    /// Enables the per-iteration timeout and starts the watchdog thread if needed, a
+    /// timed out iteration stops the whole session if `exit_on_timeout` is set
+    pub(crate) fn enable_watchdog(&mut self, timeout: Duration, exit_on_timeout: bool) {
+        self.watchdog.timeout = Some(timeout);
+        {
+            let mut slot = self.watchdog.slot.lock().unwrap();
+            slot.crashes_directory = self.trace.crashes_directory().map(PathBuf::from);
+            slot.thread_id = self.trace.thread_id();
+            slot.stop = self.stop.clone();
+            slot.exit_on_timeout = exit_on_timeout;
+        }
+        let watched = WATCHED_ITERATIONS.get_or_init(|| {
+            thread::spawn(watch_iterations);
+            Mutex::new(Vec::new())
+        });
+        watched
+            .lock()
+            .unwrap()
+            .push(Arc::downgrade(&self.watchdog.slot));
+    }
+
+    /// Starts measuring the current iteration
+    pub(crate) fn start_iteration_watch(&mut self) {
+        let Some(timeout) = self.watchdog.timeout else {
+            return;
+        };
+        let started = Instant::now();
+        self.watchdog.start(started);
+
+        let mut slot = self.watchdog.slot.lock().unwrap();
+        slot.iteration = Some(WatchedIteration {
+            started,
+            seed: self.get_current_seed(),
+            timeout,
+            flow: None,
+            flow_calls: 0,
+            init_seed: None,
+        });
+        if self
+            .watchdog
+            .published
+            .is_none_or(|published| published.elapsed() >= PUBLISH_INTERVAL)
+        {
+            slot.fuzzing_data = Some(self.fuzzing_data.clone());
+            self.watchdog.published = Some(started);
+        }
+    }
+
+    /// Shares the fuzzing data with the watchdog thread at the start of the next
+    /// iteration, regardless of `PUBLISH_INTERVAL`, used for new findings
+    pub(crate) fn publish_findings_to_watchdog(&mut self) {
+        self.watchdog.published = None;
+    }
+
+    /// Stops measuring the current iteration
+    pub(crate) fn stop_iteration_watch(&mut self) {
+        self.watchdog.stop();
+    }
+
+    /// Remembers the flow the timeout is attributed to
+    pub(crate) fn watch_flow(&mut self, flow_name: &str) {
+        if self.watchdog.started.is_some() {
+            self.watchdog.flow = Some(flow_name.to_string());
+            if let Some(iteration) = self.watchdog.slot.lock().unwrap().iteration.as_mut() {
+                iteration.flow = Some(flow_name.to_string());
+                iteration.flow_calls = self.iteration_flow_calls() + 1;
+                iteration.init_seed = self.restored_init_snapshot_seed();
+            }
+        }
+    }
+
+    /// Aborts the current iteration if it exceeded the iteration timeout
+    pub(crate) fn check_iteration_timeout(&mut self) {
+        self.watchdog.check_iteration_timeout();
+    }
+}
+
+/// Waits until the fuzzer thread finishes, or until the watchdog thread abandons it
+/// because it hung in an iteration
+pub(crate) fn wait_for_fuzzer<T>(
+    handle: thread::JoinHandle<T>,
+    thread_id: Option<usize>,
+) -> FuzzerOutcome<T> {
+    loop {
+        if let Some(fuzzing_data) = take_hung_fuzzing_data(thread_id) {
+            return FuzzerOutcome::Hung(fuzzing_data);
+        }
+        if handle.is_finished() {
+            return FuzzerOutcome::Finished(handle.join());
+        }
+        thread::sleep(WATCHDOG_INTERVAL);
+    }
+}
+
+/// Abandons the iterations which did not return control to Trident within twice the
+/// iteration timeout
+fn watch_iterations() {
+    loop {
+        thread::sleep(WATCHDOG_INTERVAL);
+        for slot in watched_slots() {
+            let mut fuzzer = slot.lock().unwrap();
+            if fuzzer.iteration.as_ref().is_some_and(is_hung) {
+                record_hung_iteration(&mut fuzzer);
+            }
+        }
+    }
+}
+
+fn is_hung(iteration: &WatchedIteration) -> bool {
+    iteration.started.elapsed() > iteration.timeout * 2
+}
+
+fn watched_slots() -> Vec<Arc<WatchedSlot>> {
+    let Some(watched) = WATCHED_ITERATIONS.get() else {
+        return Vec::new();
+    };
+    let mut watched = watched.lock().unwrap();
+    watched.retain(|slot| slot.strong_count() > 0);
+    watched.iter().filter_map(Weak::upgrade).collect()
+}
+
+/// Takes the fuzzing data of the fuzzer of the given thread if it was abandoned
+fn take_hung_fuzzing_data(thread_id: Option<usize>) -> Option<TridentFuzzingData> {
+    watched_slots().into_iter().find_map(|slot| {
+        let mut fuzzer = slot.lock().unwrap();
+        if fuzzer.hung && fuzzer.thread_id == thread_id {
+            fuzzer.hung = false;
+            Some(fuzzer.fuzzing_data.take().unwrap_or_default())
+        } else {
+            None
+        }
+    })
+}
+
+/// Adds the timeout finding to the fuzzing data of the hung fuzzer, writes the crash
+/// artifact and abandons the fuzzer, the iteration cannot be executed once more to
+/// collect its trace. Stops the session if `exit_on_timeout` is set.
+fn record_hung_iteration(fuzzer: &mut WatchedFuzzer) {
+    let Some(iteration) = fuzzer.iteration.take() else {
+        return;
+    };
+    fuzzer.hung = true;
+    if fuzzer.exit_on_timeout {
+        fuzzer.stop.stop(StopReason::Timeout);
+    }
+
+    let elapsed = iteration.started.elapsed();
+    let location = iteration.flow.unwrap_or_else(|| "init".to_string());
+    let message = format!(
+        "iteration timed out after {:.1?} without returning control to Trident",
+        elapsed
+    );
+    eprintln!(
+        "Timeout in {}: {} (seed: {}), abandoning the fuzzer",
+        location,
+        message,
+        hex::encode(iteration.seed)
+    );
+
+    let fuzzing_data = fuzzer.fuzzing_data.get_or_insert_with(Default::default);
+    fuzzing_data.add_timeout_finding(&location, &message, &iteration.seed, iteration.flow_calls);
+
+    let Some(directory) = &fuzzer.crashes_directory else {
+        return;
+    };
+    let artifact = CrashArtifact {
+        master_seed: fuzzing_data.get_master_seed().map(str::to_string),
+        iteration_seed: hex::encode(iteration.seed),
+        thread_id: fuzzer.thread_id,
+        location,
+        message,
+        init_seed: iteration.init_seed.map(hex::encode),
+        ..Default::default()
+    };
+    match artifact.save(directory) {
+        Ok(path) => eprintln!("Crash artifact: {}", path.display()),
+        Err(e) => eprintln!("Warning: Failed to save crash artifact: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    use super::*;
+
+    fn watchdog(timeout: Duration, started: Instant) -> Watchdog {
+        let mut watchdog = Watchdog {
+            timeout: Some(timeout),
+            ..Default::default()
+        };
+        watchdog.start(started);
+        watchdog.flow = Some("flow_a".to_string());
+        watchdog
+    }
+
+    fn hung_fuzzer(exit_on_timeout: bool) -> WatchedFuzzer {
+        WatchedFuzzer {
+            iteration: Some(WatchedIteration {
+                started: Instant::now() - Duration::from_millis(300),
+                seed: [7; 32],
+                timeout: Duration::from_millis(100),
+                flow: Some("flow_a".to_string()),
+                flow_calls: 3,
+                init_seed: None,
+            }),
+            exit_on_timeout,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_check_iteration_timeout_within_timeout() {
+        let mut watchdog = watchdog(Duration::from_secs(60), Instant::now());
+
+        watchdog.check_iteration_timeout();
+        assert!(watchdog.started.is_some());
+    }
+
+    #[test]
+    fn test_check_iteration_timeout_aborts_timed_out_iteration() {
+        let started = Instant::now() - Duration::from_millis(50);
+        let mut watchdog = watchdog(Duration::from_millis(10), started);
+
+        let panic = catch_unwind(AssertUnwindSafe(|| watchdog.check_iteration_timeout()))
+            .expect_err("timed out iteration must be aborted");
+        let timeout = panic
+            .downcast_ref::<IterationTimeout>()
+            .expect("abort must carry the iteration timeout");
+        assert_eq!(timeout.flow.as_deref(), Some("flow_a"));
+        assert!(timeout.elapsed >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_check_iteration_timeout_aborts_once() {
+        let started = Instant::now() - Duration::from_millis(50);
+        let mut watchdog = watchdog(Duration::from_millis(10), started);
+        watchdog.slot.lock().unwrap().iteration = Some(WatchedIteration {
+            started,
+            seed: [0; 32],
+            timeout: Duration::from_millis(10),
+            flow: None,
+            flow_calls: 0,
+            init_seed: None,
+        });
+
+        let first = catch_unwind(AssertUnwindSafe(|| watchdog.check_iteration_timeout()));
+        assert!(first.is_err());
+
+        // Checks of the unwinding iteration do not panic again, and the watchdog
+        // thread no longer watches it
+        let second = catch_unwind(AssertUnwindSafe(|| watchdog.check_iteration_timeout()));
+        assert!(second.is_ok());
+        assert!(watchdog.slot.lock().unwrap().iteration.is_none());
+    }
+
+    #[test]
+    fn test_check_iteration_timeout_without_timeout() {
+        let mut watchdog = Watchdog::default();
+        watchdog.start(Instant::now() - Duration::from_secs(60));
+
+        watchdog.check_iteration_timeout();
+    }
+
+    #[test]
+    fn test_is_hung_after_twice_the_timeout() {
+        let iteration = hung_fuzzer(false).iteration.unwrap();
+        assert!(is_hung(&iteration));
+
+        let iteration = WatchedIteration {
+            started: Instant::now(),
+            ..iteration
+        };
+        assert!(!is_hung(&iteration));
+    }
+
+    #[test]
+    fn test_record_hung_iteration_abandons_fuzzer() {
+        let mut fuzzer = hung_fuzzer(false);
+
+        record_hung_iteration(&mut fuzzer);
+        assert!(fuzzer.hung);
+        assert!(fuzzer.iteration.is_none());
+        assert!(!fuzzer.stop.is_stopped());
+        let fuzzing_data = fuzzer.fuzzing_data.as_ref().unwrap();
+        assert!(fuzzing_data.has_only_timeout_findings());
+
+        // The iteration is recorded only once
+        fuzzer.hung = false;
+        record_hung_iteration(&mut fuzzer);
+        assert!(!fuzzer.hung);
+    }
+
+    #[test]
+    fn test_record_hung_iteration_stops_session_on_exit_on_timeout() {
+        let mut fuzzer = hung_fuzzer(true);
+        let stop = fuzzer.stop.clone();
+
+        record_hung_iteration(&mut fuzzer);
+        assert_eq!(stop.reason(), Some(StopReason::Timeout));
+    }
+}
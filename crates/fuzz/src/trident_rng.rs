@@ -15,22 +15,111 @@ use solana_sdk::signer::Signer;
 
 pub struct TridentRng {
     seed: [u8; 32],
-    rng: SmallRng,
+    rng: InputRng,
 }
 
 impl Default for TridentRng {
     fn default() -> Self {
         Self {
             seed: [0; 32],
-            rng: SmallRng::from_seed([0; 32]),
+            rng: InputRng::new([0; 32]),
         }
     }
 }
 
+/// Byte source backing `TridentRng`.
+///
+/// Bytes are taken from the input buffer first (if any is set) and from the seeded
+/// `SmallRng` once the buffer is exhausted. When recording is enabled, every value handed
+/// out is stored, so the recorded bytes used as an input replay the same decisions.
+/// Without an input and recording this is a plain pass-through to `SmallRng`, so seeds
+/// stay reproducible.
+struct InputRng {
+    rng: SmallRng,
+    input: Vec<u8>,
+    position: usize,
+    recorded: Option<Vec<u8>>,
+    captured: Option<Vec<u8>>,
+}
+
+impl InputRng {
+    fn new(seed: [u8; 32]) -> Self {
+        Self {
+            rng: SmallRng::from_seed(seed),
+            input: Vec::new(),
+            position: 0,
+            recorded: None,
+            captured: None,
+        }
+    }
+
+    fn reseed(&mut self, seed: [u8; 32]) {
+        self.rng = SmallRng::from_seed(seed);
+    }
+
+    fn take_input<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let end = self.position + N;
+        if end <= self.input.len() {
+            let mut bytes = [0; N];
+            bytes.copy_from_slice(&self.input[self.position..end]);
+            self.position = end;
+            Some(bytes)
+        } else {
+            // Partial values are not used, the rest of the iteration falls back to the rng
+            self.position = self.input.len();
+            None
+        }
+    }
+
+    fn record(&mut self, bytes: &[u8]) {
+        if let Some(recorded) = self.recorded.as_mut() {
+            recorded.extend_from_slice(bytes);
+        }
+        if let Some(captured) = self.captured.as_mut() {
+            captured.extend_from_slice(bytes);
+        }
+    }
+}
+
+impl RngCore for InputRng {
+    fn next_u32(&mut self) -> u32 {
+        let value = match self.take_input::<4>() {
+            Some(bytes) => u32::from_le_bytes(bytes),
+            None => self.rng.next_u32(),
+        };
+        self.record(&value.to_le_bytes());
+        value
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = match self.take_input::<8>() {
+            Some(bytes) => u64::from_le_bytes(bytes),
+            None => self.rng.next_u64(),
+        };
+        self.record(&value.to_le_bytes());
+        value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let from_input = (self.input.len() - self.position).min(dest.len());
+        dest[..from_input].copy_from_slice(&self.input[self.position..self.position + from_input]);
+        self.position += from_input;
+        if from_input < dest.len() {
+            self.rng.fill_bytes(&mut dest[from_input..]);
+        }
+        self.record(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
 impl TridentRng {
     pub(crate) fn set_master_seed_for_debug(&mut self, seed: [u8; 32]) {
         self.seed = seed;
-        self.rng = SmallRng::from_seed(self.seed);
+        self.rng.reseed(self.seed);
     }
 
     pub(crate) fn set_master_seed_and_thread_id(&mut self, seed: [u8; 32], thread_id: usize) {
@@ -44,7 +133,7 @@ impl TridentRng {
         let final_hash = combined_hasher.finalize();
 
         self.seed = final_hash.into();
-        self.rng = SmallRng::from_seed(self.seed);
+        self.rng.reseed(self.seed);
     }
 
     pub(crate) fn rotate_seed(&mut self) {
@@ -53,7 +142,58 @@ impl TridentRng {
         temp_rng.fill_bytes(&mut new_seed);
 
         self.seed = new_seed;
-        self.rng = SmallRng::from_seed(self.seed);
+        self.rng.reseed(self.seed);
+    }
+
+    /// Sets the input consumed before falling back to the seeded rng.
+    pub(crate) fn set_input(&mut self, input: Vec<u8>) {
+        self.rng.input = input;
+        self.rng.position = 0;
+    }
+
+    /// Sets the input consumed before falling back to the seeded rng and starts
+    /// recording all values handed out during the iteration.
+    pub(crate) fn set_input_and_record(&mut self, input: Vec<u8>) {
+        self.set_input(input);
+        self.rng.recorded = Some(Vec::new());
+    }
+
+    /// Restarts the current input from its beginning, restarting the recording as well.
+    pub(crate) fn rewind_input(&mut self) {
+        self.rng.position = 0;
+        if let Some(recorded) = self.rng.recorded.as_mut() {
+            recorded.clear();
+        }
+    }
+
+    /// Returns the values recorded so far, if recording is enabled.
+    pub(crate) fn recorded_input(&self) -> Option<&[u8]> {
+        self.rng.recorded.as_deref()
+    }
+
+    /// Stops recording and returns the recorded bytes, clearing the current input.
+    pub(crate) fn take_recorded_input(&mut self) -> Vec<u8> {
+        self.rng.input.clear();
+        self.rng.position = 0;
+        self.rng.recorded.take().unwrap_or_default()
+    }
+
+    /// Draws a value and returns it together with the bytes it was generated from.
+    pub(crate) fn capture<T>(&mut self, draw: impl FnOnce(&mut Self) -> T) -> (T, Vec<u8>) {
+        self.rng.captured = Some(Vec::new());
+        let value = draw(self);
+        (value, self.rng.captured.take().unwrap_or_default())
+    }
+
+    /// Draws a value from the given bytes, falling back to the seeded rng if they run out.
+    /// The current input is restored afterwards.
+    pub(crate) fn replay<T>(&mut self, bytes: Vec<u8>, draw: impl FnOnce(&mut Self) -> T) -> T {
+        let input = std::mem::replace(&mut self.rng.input, bytes);
+        let position = std::mem::replace(&mut self.rng.position, 0);
+        let value = draw(self);
+        self.rng.input = input;
+        self.rng.position = position;
+        value
     }
 
     pub(crate) fn get_seed(&self) -> [u8; 32] {
@@ -95,4 +235,8 @@ impl TridentRng {
     pub(crate) fn gen_bool(&mut self) -> bool {
         self.rng.gen_bool(0.5)
     }
+
+    pub(crate) fn gen_bool_with_probability(&mut self, probability: f64) -> bool {
+        self.rng.gen_bool(probability)
+    }
 }
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::files::write_atomically;
+
+/// Reproduction data of a single failing iteration
+///
+/// Written to the crashes directory of the fuzz test whenever a flow panics
+/// (failed assertion, invariant or unhandled panic in the fuzz test), or by
+/// `trident fuzz minimize` with the minimized sequence of flow calls.
+/// `trident fuzz debug <target> <path to the artifact>` replays the iteration.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct CrashArtifact {
+    /// Master seed of the fuzzing session, if known
+    pub master_seed: Option<String>,
+    /// Seed of the failing iteration
+    pub iteration_seed: String,
+    /// Fuzzing thread which executed the iteration
+    pub thread_id: Option<usize>,
+    /// Panic location in the form of `file:line:column`
+    pub location: String,
+    /// Panic message
+    pub message: String,
+    /// Flows executed in the iteration, in order, including the failing one
+    pub flows: Vec<String>,
+    /// Transactions processed in the iteration up to the failure
+    pub transactions: Vec<CrashTransaction>,
+    /// Hex encoded RNG input of the iteration (execution feedback mode only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    /// Flows enabled in the iteration by swarm mode (swarm mode only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swarm: Option<Vec<String>>,
+    /// Flow weights learned by adaptive flow selection (`[fuzz.flows] adaptive` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<BTreeMap<String, u32>>,
+    /// Seed of the iteration which executed the `#[init]` method, if the failing iteration
+    /// restored its snapshot (`#[init(snapshot)]` only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub init_seed: Option<String>,
+    /// Minimized sequence of flow calls (minimized artifacts only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<CrashFlowStep>>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct CrashTransaction {
+    /// Name the transaction was logged as
+    pub name: Option<String>,
+    /// Flow which processed the transaction
+    pub flow: Option<String>,
+    /// Result of the transaction
+    pub result: String,
+    /// Transaction logs
+    pub logs: Vec<String>,
+}
+
+/// A single flow call of a minimized sequence
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct CrashFlowStep {
+    /// Name of the flow
+    pub flow: String,
+    /// Index of the selected flow, `None` for `#[init]` and `#[end]` flows
+    pub selection: Option<u64>,
+    /// Hex encoded RNG input consumed by the flow
+    pub input: String,
+}
+
+impl CrashArtifact {
+    /// Saves the artifact into the directory as `crash_<iteration seed>.json`,
+    /// or `minimized_<iteration seed>.json` if it contains a minimized sequence
+    ///
+    /// # Returns
+    /// Path of the saved artifact
+    pub fn save(&self, directory: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(directory)?;
+        let prefix = if self.steps.is_some() {
+            "minimized"
+        } else {
+            "crash"
+        };
+        let path = directory.join(format!("{}_{}.json", prefix, self.iteration_seed));
+        let serialized = serde_json::to_string_pretty(self)?;
+        write_atomically(&path, serialized)?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
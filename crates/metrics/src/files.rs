@@ -0,0 +1,30 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Counter making the names of temporary files unique within the process
+static TEMPORARY_FILES: AtomicU64 = AtomicU64::new(0);
+
+/// Writes the file through a temporary file in the same directory, which is then renamed
+///
+/// Other fuzzing processes sharing the directory never see a partially written file.
+/// Temporary files are hidden (their names start with a dot), so readers listing the
+/// directory should skip hidden files.
+pub fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing file name"))?;
+    let temporary = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temporary);
+    })
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use prettytable::row;
+use prettytable::Table;
+
+use crate::normalize::normalize_message;
+use crate::types::Seed;
+
+/// Result of adding a failure to the findings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingUpdate {
+    /// The failure has a signature not seen before
+    New,
+    /// The failure is a known finding, reproduced with fewer flow calls than before
+    ShorterReproducer,
+    /// The failure is a known finding
+    Known,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum FindingKind {
+    /// Panic of the fuzz test (failed assertion, invariant or unhandled panic)
+    Panic,
+    /// Panic of a program invoked by a transaction
+    TransactionPanic,
+    /// Iteration which exceeded the iteration timeout
+    Timeout,
+    /// Writable account left in an invalid state by a successful transaction
+    AccountState,
+    /// Transaction which changed the sum of lamports of its accounts by other than its fee
+    LamportConservation,
+    /// Transaction which also succeeded with one of its accounts substituted
+    AccountSubstitution,
+    /// Named transaction which consumed more compute units than its budget
+    ComputeUnitBudget,
+}
+
+/// Unique failures of a fuzzing session
+///
+/// Failures are deduplicated by their signature, which is the panic location and
+/// normalized panic message for panics of the fuzz test, or the instruction name and
+/// normalized panic log for panics of a program. Numbers and account addresses are
+/// removed from messages, so failures printing values map to a single finding.
+/// Invalid account states, lamport conservation violations and accepted account
+/// substitutions are identified by the transaction name and the violation.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub(crate) struct Findings {
+    findings: BTreeMap<String, Finding>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub(crate) struct Finding {
+    kind: FindingKind,
+    /// Panic location, or name of the instruction
+    location: String,
+    /// Message of the first occurrence
+    message: String,
+    hits: u64,
+    first_seen_seed: String,
+    /// Milliseconds since the Unix epoch of the first occurrence
+    first_seen_at: u128,
+    /// Seed of the iteration which reproduced the finding with the fewest flow calls
+    reproducer_seed: String,
+    reproducer_flow_calls: u64,
+}
+
+impl Findings {
+    pub(crate) fn add(
+        &mut self,
+        kind: FindingKind,
+        location: &str,
+        message: &str,
+        seed: &Seed,
+        flow_calls: u64,
+    ) -> FindingUpdate {
+        let signature = format!("{:?}|{}|{}", kind, location, normalize_message(message));
+        match self.findings.get_mut(&signature) {
+            Some(finding) => {
+                finding.hits += 1;
+                if flow_calls < finding.reproducer_flow_calls {
+                    finding.reproducer_seed = hex::encode(seed);
+                    finding.reproducer_flow_calls = flow_calls;
+                    FindingUpdate::ShorterReproducer
+                } else {
+                    FindingUpdate::Known
+                }
+            }
+            None => {
+                self.findings.insert(
+                    signature,
+                    Finding {
+                        kind,
+                        location: location.to_string(),
+                        message: message.to_string(),
+                        hits: 1,
+                        first_seen_seed: hex::encode(seed),
+                        first_seen_at: SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map(|elapsed| elapsed.as_millis())
+                            .unwrap_or_default(),
+                        reproducer_seed: hex::encode(seed),
+                        reproducer_flow_calls: flow_calls,
+                    },
+                );
+                FindingUpdate::New
+            }
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Returns `true` if there are findings and all of them are timeouts
+    pub(crate) fn only_timeouts(&self) -> bool {
+        !self.findings.is_empty()
+            && self
+                .findings
+                .values()
+                .all(|finding| finding.kind == FindingKind::Timeout)
+    }
+
+    pub(crate) fn concat(&mut self, other: &Findings) {
+        for (signature, other_finding) in &other.findings {
+            self.findings
+                .entry(signature.clone())
+                .and_modify(|finding| {
+                    finding.hits += other_finding.hits;
+                    if other_finding.first_seen_at < finding.first_seen_at {
+                        finding.message = other_finding.message.clone();
+                        finding.first_seen_seed = other_finding.first_seen_seed.clone();
+                        finding.first_seen_at = other_finding.first_seen_at;
+                    }
+                    if other_finding.reproducer_flow_calls < finding.reproducer_flow_calls {
+                        finding.reproducer_seed = other_finding.reproducer_seed.clone();
+                        finding.reproducer_flow_calls = other_finding.reproducer_flow_calls;
+                    }
+                })
+                .or_insert_with(|| other_finding.clone());
+        }
+    }
+
+    /// Displays the unique findings in a formatted table, most frequent first.
+    pub(crate) fn show_table(&self) {
+        let mut findings: Vec<&Finding> = self.findings.values().collect();
+        findings.sort_by(|a, b| b.hits.cmp(&a.hits));
+
+        let mut table = Table::new();
+        table.add_row(row![
+            "Finding",
+            "Location",
+            "Message",
+            "Hits",
+            "First Seen Seed",
+            "Reproducer Seed",
+            "Reproducer Flow Calls",
+        ]);
+        for finding in findings {
+            let kind = match finding.kind {
+                FindingKind::Panic => "Panic",
+                FindingKind::TransactionPanic => "Transaction Panic",
+                FindingKind::Timeout => "Timeout",
+                FindingKind::AccountState => "Account State",
+                FindingKind::LamportConservation => "Lamport Conservation",
+                FindingKind::AccountSubstitution => "Account Substitution",
+                FindingKind::ComputeUnitBudget => "Compute Unit Budget",
+            };
+            table.add_row(row![
+                kind,
+                finding.location,
+                finding.message,
+                finding.hits,
+                finding.first_seen_seed,
+                finding.reproducer_seed,
+                finding.reproducer_flow_calls,
+            ]);
+        }
+        table.printstd();
+    }
+
+    pub(crate) fn to_dashboard_format(&self) -> serde_json::Value {
+        let mut findings: Vec<&Finding> = self.findings.values().collect();
+        findings.sort_by(|a, b| b.hits.cmp(&a.hits));
+        serde_json::to_value(findings).unwrap_or_default()
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use prettytable::row;
+use prettytable::Table;
+
+/// Outcome of a single flow call, used to adapt the flow weights
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowCallOutcome {
+    /// A transaction of the flow call reached new behavior (new execution feature or
+    /// new custom error code)
+    Novel,
+    /// At least one transaction of the flow call succeeded
+    Productive,
+    /// All transactions of the flow call failed
+    Failing,
+    /// The flow call executed no transaction, the weight is kept
+    Idle,
+}
+
+/// Weights learned by the adaptive flow selection
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub(crate) struct FlowWeights {
+    flows: BTreeMap<String, FlowWeight>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub(crate) struct FlowWeight {
+    base_weight: u32,
+    /// Learned weight after the last flow call
+    learned_weight: f64,
+    calls: u64,
+    novel_calls: u64,
+    productive_calls: u64,
+    failing_calls: u64,
+}
+
+impl FlowWeights {
+    pub(crate) fn record(
+        &mut self,
+        flow: &str,
+        base_weight: u32,
+        learned_weight: f64,
+        outcome: FlowCallOutcome,
+    ) {
+        let weight = self.flows.entry(flow.to_string()).or_default();
+        weight.base_weight = base_weight;
+        weight.learned_weight = learned_weight;
+        weight.calls += 1;
+        match outcome {
+            FlowCallOutcome::Novel => weight.novel_calls += 1,
+            FlowCallOutcome::Productive => weight.productive_calls += 1,
+            FlowCallOutcome::Failing => weight.failing_calls += 1,
+            FlowCallOutcome::Idle => {}
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.flows.is_empty()
+    }
+
+    /// Merges weights learned by another thread, learned weights are averaged by calls
+    pub(crate) fn concat(&mut self, other: &FlowWeights) {
+        for (flow, other_weight) in &other.flows {
+            self.flows
+                .entry(flow.clone())
+                .and_modify(|weight| {
+                    let calls = weight.calls + other_weight.calls;
+                    if calls > 0 {
+                        weight.learned_weight = (weight.learned_weight * weight.calls as f64
+                            + other_weight.learned_weight * other_weight.calls as f64)
+                            / calls as f64;
+                    }
+                    weight.calls = calls;
+                    weight.novel_calls += other_weight.novel_calls;
+                    weight.productive_calls += other_weight.productive_calls;
+                    weight.failing_calls += other_weight.failing_calls;
+                })
+                .or_insert_with(|| other_weight.clone());
+        }
+    }
+
+    /// Displays the learned weights in a formatted table, highest share first.
+    pub(crate) fn show_table(&self) {
+        let total: f64 = self
+            .flows
+            .values()
+            .map(|weight| weight.learned_weight)
+            .sum();
+        let mut flows: Vec<(&String, &FlowWeight)> = self.flows.iter().collect();
+        flows.sort_by(|a, b| b.1.learned_weight.total_cmp(&a.1.learned_weight));
+
+        let mut table = Table::new();
+        table.add_row(row![
+            "Flow",
+            "Base Weight",
+            "Learned Share",
+            "Calls",
+            "Novel Calls",
+            "Productive Calls",
+            "Failing Calls",
+        ]);
+        for (flow, weight) in flows {
+            table.add_row(row![
+                flow,
+                weight.base_weight,
+                format!(
+                    "{:.1}%",
+                    100.0 * weight.learned_weight / total.max(f64::EPSILON)
+                ),
+                weight.calls,
+                weight.novel_calls,
+                weight.productive_calls,
+                weight.failing_calls,
+            ]);
+        }
+        table.printstd();
+    }
+
+    pub(crate) fn to_dashboard_format(&self) -> serde_json::Value {
+        serde_json::to_value(&self.flows).unwrap_or_default()
+    }
+}
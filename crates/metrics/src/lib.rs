@@ -1,25 +1,48 @@
 #![allow(dead_code)]
 
+mod crash;
+mod files;
+mod findings;
+mod flow_weights;
+mod normalize;
 mod regression;
+mod replay;
 mod transactions;
 pub mod types;
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 
 use solana_sdk::account::AccountSharedData;
 use types::Seed;
 
+use crate::findings::FindingKind;
+use crate::findings::Findings;
+use crate::flow_weights::FlowWeights;
 use crate::regression::regression::FuzzingRegression;
 use crate::transactions::transaction_stats::FuzzingStatistics;
 
+pub use crate::crash::CrashArtifact;
+pub use crate::crash::CrashFlowStep;
+pub use crate::crash::CrashTransaction;
+pub use crate::files::write_atomically;
+pub use crate::findings::FindingUpdate;
+pub use crate::flow_weights::FlowCallOutcome;
+pub use crate::normalize::collapse_digits;
+pub use crate::normalize::is_address_like;
 pub use crate::regression::compare::compare_regression_files;
 pub use crate::regression::compare::ComparisonResult;
+pub use crate::replay::ReplayFile;
+pub use crate::replay::ReplayStep;
+pub use crate::replay::ReplayValue;
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct TridentFuzzingData {
     master_seed: Option<String>,
     metrics: FuzzingStatistics,
     regression: FuzzingRegression,
+    findings: Findings,
+    flow_weights: FlowWeights,
 }
 
 // Metrics
@@ -40,6 +63,10 @@ impl TridentFuzzingData {
         self.metrics.add_successful_transaction(transaction_name);
     }
 
+    pub fn add_compute_units(&mut self, transaction_name: &str, units: u64) {
+        self.metrics.add_compute_units(transaction_name, units);
+    }
+
     pub fn add_failed_transaction(
         &mut self,
         transaction_name: &str,
@@ -78,6 +105,176 @@ impl TridentFuzzingData {
     }
 }
 
+// Findings
+impl TridentFuzzingData {
+    /// Adds a panic of the fuzz test to the unique findings
+    pub fn add_panic_finding(
+        &mut self,
+        location: &str,
+        message: &str,
+        seed: &Seed,
+        flow_calls: u64,
+    ) -> FindingUpdate {
+        self.findings
+            .add(FindingKind::Panic, location, message, seed, flow_calls)
+    }
+
+    /// Adds a panic of a program to the unique findings
+    ///
+    /// The finding is identified by the panic log of the program if present
+    /// (for example `Program log: panicked at ...`), otherwise by `panic`.
+    pub fn add_transaction_panic_finding(
+        &mut self,
+        transaction_name: &str,
+        panic: &str,
+        logs: Option<&[String]>,
+        seed: &Seed,
+        flow_calls: u64,
+    ) -> FindingUpdate {
+        let panic_log = logs
+            .unwrap_or_default()
+            .iter()
+            .find(|line| line.contains("panicked at"))
+            .map_or(panic, String::as_str);
+        self.findings.add(
+            FindingKind::TransactionPanic,
+            transaction_name,
+            panic_log,
+            seed,
+            flow_calls,
+        )
+    }
+
+    /// Adds a writable account left in an invalid state by a transaction to the unique findings
+    ///
+    /// The finding is identified by the transaction name and the violation.
+    pub fn add_account_state_finding(
+        &mut self,
+        transaction_name: &str,
+        violation: &str,
+        seed: &Seed,
+        flow_calls: u64,
+    ) -> FindingUpdate {
+        self.findings.add(
+            FindingKind::AccountState,
+            transaction_name,
+            violation,
+            seed,
+            flow_calls,
+        )
+    }
+
+    /// Adds a transaction which created or destroyed lamports to the unique findings
+    ///
+    /// The finding is identified by the transaction name and the violation.
+    pub fn add_lamport_conservation_finding(
+        &mut self,
+        transaction_name: &str,
+        violation: &str,
+        seed: &Seed,
+        flow_calls: u64,
+    ) -> FindingUpdate {
+        self.findings.add(
+            FindingKind::LamportConservation,
+            transaction_name,
+            violation,
+            seed,
+            flow_calls,
+        )
+    }
+
+    /// Adds a transaction which also succeeded with a substituted account to the unique findings
+    ///
+    /// The finding is identified by the transaction name and the substitution.
+    pub fn add_account_substitution_finding(
+        &mut self,
+        transaction_name: &str,
+        substitution: &str,
+        seed: &Seed,
+        flow_calls: u64,
+    ) -> FindingUpdate {
+        self.findings.add(
+            FindingKind::AccountSubstitution,
+            transaction_name,
+            substitution,
+            seed,
+            flow_calls,
+        )
+    }
+
+    /// Adds a transaction which exceeded its compute unit budget to the unique findings
+    ///
+    /// The finding is identified by the transaction name and the violation.
+    pub fn add_compute_unit_budget_finding(
+        &mut self,
+        transaction_name: &str,
+        violation: &str,
+        seed: &Seed,
+        flow_calls: u64,
+    ) -> FindingUpdate {
+        self.findings.add(
+            FindingKind::ComputeUnitBudget,
+            transaction_name,
+            violation,
+            seed,
+            flow_calls,
+        )
+    }
+
+    /// Adds an iteration which exceeded the iteration timeout to the unique findings
+    ///
+    /// The finding is identified by the flow which was running when the timeout fired.
+    pub fn add_timeout_finding(
+        &mut self,
+        flow: &str,
+        message: &str,
+        seed: &Seed,
+        flow_calls: u64,
+    ) -> FindingUpdate {
+        self.findings
+            .add(FindingKind::Timeout, flow, message, seed, flow_calls)
+    }
+
+    /// Returns `true` if timeouts are the only findings of the session
+    pub fn has_only_timeout_findings(&self) -> bool {
+        self.findings.only_timeouts()
+    }
+
+    /// Returns `true` if any failure was found
+    pub fn has_findings(&self) -> bool {
+        !self.findings.is_empty()
+    }
+
+    /// Displays the unique findings in a formatted table, if there are any.
+    pub fn show_findings(&self) {
+        if !self.findings.is_empty() {
+            self.findings.show_table();
+        }
+    }
+}
+
+// Adaptive flow weights
+impl TridentFuzzingData {
+    /// Records a flow call of the adaptive flow selection with the weight learned after it
+    pub fn add_flow_call(
+        &mut self,
+        flow: &str,
+        base_weight: u32,
+        learned_weight: f64,
+        outcome: FlowCallOutcome,
+    ) {
+        self.flow_weights
+            .record(flow, base_weight, learned_weight, outcome);
+    }
+
+    /// Displays the learned flow weights in a formatted table, if adaptive selection was used.
+    pub fn show_flow_weights(&self) {
+        if !self.flow_weights.is_empty() {
+            self.flow_weights.show_table();
+        }
+    }
+}
+
 // Regression
 impl TridentFuzzingData {
     pub fn add_to_regression(
@@ -98,11 +295,17 @@ impl TridentFuzzingData {
             master_seed: Some(hex::encode(seed)),
             metrics: FuzzingStatistics::new(),
             regression: FuzzingRegression::default(),
+            findings: Findings::default(),
+            flow_weights: FlowWeights::default(),
         }
     }
     pub fn add_master_seed(&mut self, seed: &str) {
         self.master_seed = Some(seed.to_string());
     }
+
+    pub fn get_master_seed(&self) -> Option<&str> {
+        self.master_seed.as_deref()
+    }
 }
 
 // Generation
@@ -111,6 +314,8 @@ impl TridentFuzzingData {
         // Generate metrics JSON if FUZZING_METRICS is set
         if std::env::var("FUZZING_METRICS").is_ok() {
             self.metrics.show_table();
+            self.show_findings();
+            self.show_flow_weights();
 
             if let Ok(metrics_file_name) = std::env::var("FUZZING_JSON") {
                 self.to_json(&metrics_file_name);
@@ -137,6 +342,10 @@ impl TridentFuzzingData {
         let mut metrics_copy = self.metrics.clone();
         for metric in metrics_copy.custom_metrics.values_mut() {
             metric.finalize_histogram();
+            metric.clear_values();
+        }
+        for stats in metrics_copy.transactions.values_mut() {
+            stats.compute_units.finalize();
         }
 
         // Create the data structure for JSON serialization with clean structure
@@ -154,6 +363,13 @@ impl TridentFuzzingData {
             "custom_metrics".to_string(),
             serde_json::to_value(&metrics_copy.custom_metrics).unwrap_or_default(),
         );
+        json_data.insert("findings".to_string(), self.findings.to_dashboard_format());
+        if !self.flow_weights.is_empty() {
+            json_data.insert(
+                "flow_weights".to_string(),
+                self.flow_weights.to_dashboard_format(),
+            );
+        }
 
         // Include state hash at the end if regression is enabled and state exists
         if let Ok(state_hash) = self.regression.get_snapshots_hash() {
@@ -187,6 +403,7 @@ impl TridentFuzzingData {
         let mut custom_metrics_for_display = self.metrics.custom_metrics.clone();
         for metric in custom_metrics_for_display.values_mut() {
             metric.finalize_histogram();
+            metric.clear_values();
         }
         let mut instructions = serde_json::Map::new();
 
@@ -217,6 +434,10 @@ impl TridentFuzzingData {
                 custom_instruction_errors,
             );
             instruction_data.insert("transactions_panics".to_string(), transactions_panics);
+            instruction_data.insert(
+                "compute_units".to_string(),
+                stats.compute_units.to_dashboard_format(),
+            );
 
             instructions.insert(transaction_name.clone(), instruction_data.into());
         }
@@ -227,6 +448,13 @@ impl TridentFuzzingData {
             "custom_metrics".to_string(),
             serde_json::to_value(&custom_metrics_for_display).unwrap_or_default(),
         );
+        result.insert("findings".to_string(), self.findings.to_dashboard_format());
+        if !self.flow_weights.is_empty() {
+            result.insert(
+                "flow_weights".to_string(),
+                self.flow_weights.to_dashboard_format(),
+            );
+        }
 
         // Always include master seed (null if not set)
         result.insert("master_seed".to_string(), self.master_seed.clone().into());
@@ -245,10 +473,27 @@ impl TridentFuzzingData {
     }
 }
 
+// Worker data of distributed fuzzing
+impl TridentFuzzingData {
+    /// Saves all data of a fuzzing worker, including the values behind the histograms,
+    /// so the data of all workers can be merged exactly
+    pub fn save_worker_data(&self, path: &Path) -> std::io::Result<()> {
+        let serialized = serde_json::to_string(self)?;
+        write_atomically(path, serialized)
+    }
+
+    pub fn load_worker_data(path: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
 impl TridentFuzzingData {
     pub fn _merge(&mut self, other: TridentFuzzingData) {
         self.metrics.merge_from(&other.metrics);
         self.regression.merge_from(&other.regression);
+        self.findings.concat(&other.findings);
+        self.flow_weights.concat(&other.flow_weights);
     }
 
     pub fn get_exit_code(&self) -> i32 {
@@ -0,0 +1,83 @@
+/// Whether the token looks like a base58 account address
+pub fn is_address_like(token: &str) -> bool {
+    (32..=44).contains(&token.len()) && token.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Replaces every run of digits in the token by a single `#`
+pub fn collapse_digits(token: &str) -> String {
+    let mut collapsed = String::with_capacity(token.len());
+    let mut previous_digit = false;
+    for c in token.chars() {
+        if c.is_ascii_digit() {
+            if !previous_digit {
+                collapsed.push('#');
+            }
+            previous_digit = true;
+        } else {
+            collapsed.push(c);
+            previous_digit = false;
+        }
+    }
+    collapsed
+}
+
+/// Removes numbers and account addresses from a message
+pub(crate) fn normalize_message(message: &str) -> String {
+    message
+        .split_whitespace()
+        .map(|token| {
+            if is_address_like(token) {
+                "<address>".to_string()
+            } else {
+                collapse_digits(token)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapse_digits() {
+        assert_eq!(collapse_digits("amount"), "amount");
+        assert_eq!(collapse_digits("12345"), "#");
+        assert_eq!(collapse_digits("src/lib.rs:42:7"), "src/lib.rs:#:#");
+        assert_eq!(collapse_digits("0x1f"), "#x#f");
+    }
+
+    #[test]
+    fn test_is_address_like() {
+        assert!(is_address_like("11111111111111111111111111111111"));
+        assert!(is_address_like(
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        ));
+        assert!(!is_address_like("short"));
+        assert!(!is_address_like(
+            "Tokenkeg-QfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        ));
+        assert!(!is_address_like(
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DATokenkeg"
+        ));
+    }
+
+    #[test]
+    fn test_normalize_message() {
+        assert_eq!(
+            normalize_message(
+                "account TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA changed by +1500 lamports"
+            ),
+            "account <address> changed by +# lamports"
+        );
+        assert_eq!(
+            normalize_message("counter reached 3"),
+            normalize_message("counter reached  17")
+        );
+        assert_ne!(
+            normalize_message("counter reached 3"),
+            normalize_message("balance reached 3")
+        );
+    }
+}
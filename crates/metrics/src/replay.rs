@@ -0,0 +1,68 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::files::write_atomically;
+
+/// Recorded decisions of a single failing iteration
+///
+/// Contains the selected flows and every random value drawn by them, grouped by
+/// flow and by the `Trident` method which drew the value. Flows are replayed by their
+/// name and values by the method that draws them, so the file stays replayable after
+/// changes to the fuzz test which shift the random stream of the seed.
+/// `trident fuzz replay <target> <path to the file>` replays the iteration.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct ReplayFile {
+    /// Seed of the recorded iteration, used for values missing in the file
+    pub iteration_seed: String,
+    /// Panic location in the form of `file:line:column`
+    pub location: String,
+    /// Panic message
+    pub message: String,
+    /// Executed flows, in order, including `#[init]` and `#[end]` flows
+    pub steps: Vec<ReplayStep>,
+}
+
+/// A single flow call of a recorded iteration
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct ReplayStep {
+    /// Name of the flow
+    pub flow: String,
+    /// `true` if the flow was selected randomly, `false` for `#[init]` and `#[end]` flows
+    pub selected: bool,
+    /// Random values drawn by the flow, in order
+    pub values: Vec<ReplayValue>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct ReplayValue {
+    /// `Trident` method which drew the value (for example `random_from_range`)
+    pub method: String,
+    /// Hex encoded RNG bytes the value was generated from
+    pub bytes: String,
+}
+
+impl ReplayFile {
+    /// Saves the replay file into the directory as `replay_<iteration seed>.json`
+    ///
+    /// # Returns
+    /// Path of the saved file
+    pub fn save(&self, directory: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(directory)?;
+        let path = directory.join(format!("replay_{}.json", self.iteration_seed));
+        let serialized = serde_json::to_string_pretty(self)?;
+        write_atomically(&path, serialized)?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Number of randomly selected flow calls
+    pub fn flow_calls(&self) -> u64 {
+        self.steps.iter().filter(|step| step.selected).count() as u64
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+/// Compute units consumed by the executions of a transaction
+///
+/// Consumptions are kept as counts of distinct values, so the median is exact
+/// and merging the metrics of fuzzing threads or worker processes is cheap.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub(crate) struct ComputeUnitMetrics {
+    min: u64,
+    max: u64,
+    count: u64,
+    sum: u64,
+    median: u64, // Will be calculated when needed
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    counts: BTreeMap<u64, u64>,
+}
+
+impl ComputeUnitMetrics {
+    pub(crate) fn add(&mut self, units: u64) {
+        if self.count == 0 {
+            self.min = units;
+            self.max = units;
+        } else {
+            self.min = self.min.min(units);
+            self.max = self.max.max(units);
+        }
+        self.count += 1;
+        self.sum = self.sum.saturating_add(units);
+        *self.counts.entry(units).or_default() += 1;
+    }
+
+    pub(crate) fn concat(&mut self, other: &ComputeUnitMetrics) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count += other.count;
+        self.sum = self.sum.saturating_add(other.sum);
+        for (units, count) in &other.counts {
+            *self.counts.entry(*units).or_default() += count;
+        }
+    }
+
+    pub(crate) fn get_min(&self) -> u64 {
+        self.min
+    }
+
+    pub(crate) fn get_max(&self) -> u64 {
+        self.max
+    }
+
+    /// Lower median of the consumed compute units, `0` if nothing was recorded
+    pub(crate) fn get_median(&self) -> u64 {
+        let mut remaining = self.count.div_ceil(2);
+        for (units, count) in &self.counts {
+            if remaining <= *count {
+                return *units;
+            }
+            remaining -= count;
+        }
+        0
+    }
+
+    /// Calculates the median for serialization, the counts are only kept in worker data
+    pub(crate) fn finalize(&mut self) {
+        self.median = self.get_median();
+        self.counts.clear();
+    }
+
+    pub(crate) fn to_dashboard_format(&self) -> serde_json::Value {
+        serde_json::json!({
+            "min": self.min,
+            "max": self.max,
+            "median": self.get_median(),
+            "avg": if self.count > 0 { self.sum / self.count } else { 0 },
+            "count": self.count,
+        })
+    }
+}
@@ -10,7 +10,7 @@ pub enum CustomMetricValue {
         avg: f64,
         median: f64,
         entropy: f64,
-        #[serde(skip)]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         values: Vec<f64>,
     },
 }
@@ -110,6 +110,13 @@ impl CustomMetricValue {
         entropy
     }
 
+    /// Drops the recorded values, they are only kept in worker data
+    pub(crate) fn clear_values(&mut self) {
+        if let Self::Histogram { values, .. } = self {
+            values.clear();
+        }
+    }
+
     /// Update computed values for serialization
     pub fn finalize_histogram(&mut self) {
         if let Self::Histogram {
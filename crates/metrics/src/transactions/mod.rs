@@ -1,3 +1,4 @@
+pub(crate) mod compute_units;
 pub(crate) mod custom_metrics;
 pub(crate) mod transaction_custom_error;
 pub(crate) mod transaction_error;
@@ -4,6 +4,7 @@ use prettytable::row;
 use prettytable::Table;
 use std::collections::BTreeMap;
 
+use crate::transactions::compute_units::ComputeUnitMetrics;
 use crate::transactions::custom_metrics::CustomMetricValue;
 use crate::transactions::transaction_custom_error::TransactionCustomErrorMetrics;
 use crate::transactions::transaction_error::TransactionErrorMetrics;
@@ -20,6 +21,8 @@ pub(crate) struct TransactionStats {
     pub(crate) transactions_errors: TransactionErrorMetrics,
     pub(crate) custom_instruction_errors: TransactionCustomErrorMetrics,
     pub(crate) transactions_panics: TransactionPanicMetrics,
+    #[serde(default)]
+    pub(crate) compute_units: ComputeUnitMetrics,
 }
 
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize, Clone)]
@@ -48,6 +51,7 @@ impl FuzzingStatistics {
                 transactions_errors: TransactionErrorMetrics::default(),
                 custom_instruction_errors: TransactionCustomErrorMetrics::default(),
                 transactions_panics: TransactionPanicMetrics::default(),
+                compute_units: ComputeUnitMetrics::default(),
             });
     }
 
@@ -56,6 +60,13 @@ impl FuzzingStatistics {
             .entry(transaction.to_string())
             .and_modify(|iterations_stats| iterations_stats.transaction_successful += 1);
     }
+
+    pub(crate) fn add_compute_units(&mut self, transaction: &str, units: u64) {
+        self.transactions
+            .entry(transaction.to_string())
+            .and_modify(|iterations_stats| iterations_stats.compute_units.add(units));
+    }
+
     pub(crate) fn add_failed_transaction(
         &mut self,
         transaction: &str,
@@ -116,6 +127,9 @@ impl FuzzingStatistics {
             "Ix Success",
             "Ix Failed",
             "Instruction Panicked",
+            "CU Min",
+            "CU Median",
+            "CU Max",
         ]);
         for (instruction, stats) in &self.transactions {
             table.add_row(row![
@@ -124,6 +138,9 @@ impl FuzzingStatistics {
                 stats.transaction_successful,
                 stats.transaction_failed,
                 stats.transaction_panicked,
+                stats.compute_units.get_min(),
+                stats.compute_units.get_median(),
+                stats.compute_units.get_max(),
             ]);
         }
         table.printstd();
@@ -200,6 +217,7 @@ impl FuzzingStatistics {
                     existing_stats
                         .transactions_panics
                         .concat(&stats.transactions_panics);
+                    existing_stats.compute_units.concat(&stats.compute_units);
                 })
                 .or_insert_with(|| stats.clone());
         }
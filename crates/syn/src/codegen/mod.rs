@@ -1,2 +1,3 @@
 pub mod trident_flow_executor;
+pub mod trident_fuzz_random;
 pub mod trident_fuzz_test_methods;
@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 use quote::ToTokens;
+use syn::Ident;
 
 use crate::types::trident_flow_executor::TridentFlowExecutorImpl;
 
@@ -82,8 +83,20 @@ impl TridentFlowExecutorImpl {
     /// Generate the initialization call if an init method exists
     fn generate_init_call(&self) -> TokenStream {
         if let Some(init_method) = &self.init_method {
+            let mut init_call = Self::generate_flow_call(init_method);
+            if self.init_snapshot {
+                let init_name = init_method.to_string();
+                init_call = quote! {
+                    if !self.trident._restore_init_snapshot(#init_name, &mut self.fuzz_accounts) {
+                        #init_call
+                        self.trident._save_init_snapshot(&self.fuzz_accounts);
+                    }
+                };
+            }
+            let invariant_checks = self.generate_invariant_checks(init_method);
             quote! {
-                self.#init_method();
+                #init_call
+                #invariant_checks
             }
         } else {
             quote! {}
@@ -93,9 +106,7 @@ impl TridentFlowExecutorImpl {
     /// Generate the end call if an end method exists
     fn generate_end_call(&self) -> TokenStream {
         if let Some(end_method) = &self.end_method {
-            quote! {
-                self.#end_method();
-            }
+            Self::generate_flow_call(end_method)
         } else {
             quote! {}
         }
@@ -133,89 +144,92 @@ impl TridentFlowExecutorImpl {
             .iter()
             .any(|method| method.constraints.weight.is_some());
 
-        if has_weights {
-            // Generate weighted selection logic
-            self.generate_weighted_flow_selection(active_methods)
-        } else {
-            // Generate uniform random selection logic (original behavior)
-            self.generate_uniform_flow_selection(active_methods)
+        // Without weights every flow is selected uniformly, flows with weight 0 are skipped
+        let selectable_methods: Vec<_> = active_methods
+            .iter()
+            .filter(|method| !has_weights || method.constraints.weight.unwrap_or(0) > 0)
+            .collect();
+
+        if selectable_methods.is_empty() {
+            return quote! {
+                // All flows have weight 0, nothing to execute
+            };
         }
-    }
 
-    /// Generate uniform random flow selection (original behavior)
-    fn generate_uniform_flow_selection(
-        &self,
-        active_methods: &[&crate::types::trident_flow_executor::FlowMethod],
-    ) -> TokenStream {
-        let flow_match_arms = active_methods.iter().enumerate().map(|(index, method)| {
-            let method_ident = &method.ident;
+        let flow_candidates = selectable_methods.iter().map(|method| {
+            let flow_name = method.ident.to_string();
+            let weight = method.constraints.weight.unwrap_or(1);
+            let enabled = match &method.constraints.requires {
+                Some(guard) => quote! { self.#guard() },
+                None => quote! { true },
+            };
+            let after = &method.constraints.after;
+            let max_calls = match method.constraints.max_calls {
+                Some(max_calls) => quote! { Some(#max_calls) },
+                None => quote! { None },
+            };
             quote! {
-                #index => self.#method_ident(),
+                FlowCandidate {
+                    name: #flow_name,
+                    weight: #weight,
+                    enabled: #enabled,
+                    after: &[#(#after),*],
+                    max_calls: #max_calls,
+                }
             }
         });
-        let num_flows = active_methods.len();
+        let flow_match_arms = selectable_methods
+            .iter()
+            .enumerate()
+            .map(|(index, method)| {
+                let flow_call = Self::generate_flow_call(&method.ident);
+                let invariant_checks = self.generate_invariant_checks(&method.ident);
+                quote! {
+                    Some(#index) => {
+                        #flow_call
+                        #invariant_checks
+                    }
+                }
+            });
 
         quote! {
-            // Randomly select and execute flows for the specified number of calls
+            // Randomly select and execute flows for the specified number of calls, flows whose
+            // `requires` guard or ordering constraints are not satisfied are not selected
             for _ in 0..flow_calls_per_iteration {
-                let flow_index = self.trident.random_from_range(0..#num_flows);
-                match flow_index {
+                let flows = [#(#flow_candidates),*];
+                match self.trident._select_flow(&flows) {
                     #(#flow_match_arms)*
-                    _ => unreachable!("Invalid flow index"),
+                    Some(_) => unreachable!("Invalid flow index"),
+                    // No flow can be selected in this flow call
+                    None => {}
                 }
             }
         }
     }
 
-    /// Generate weighted flow selection logic
-    fn generate_weighted_flow_selection(
-        &self,
-        active_methods: &[&crate::types::trident_flow_executor::FlowMethod],
-    ) -> TokenStream {
-        // Filter out flows with weight 0 (they should be skipped)
-        let weighted_methods: Vec<_> = active_methods
-            .iter()
-            .filter(|method| method.constraints.weight.unwrap_or(0) > 0)
-            .collect();
-
-        if weighted_methods.is_empty() {
-            return quote! {
-                // All flows have weight 0, nothing to execute
-            };
+    /// Generate a flow method call, recording the flow name for crash artifacts
+    fn generate_flow_call(method_ident: &Ident) -> TokenStream {
+        let flow_name = method_ident.to_string();
+        quote! {
+            self.trident._record_flow(#flow_name);
+            self.#method_ident();
         }
+    }
 
-        // Calculate total weight
-        let total_weight: u32 = weighted_methods
-            .iter()
-            .map(|method| method.constraints.weight.unwrap())
-            .sum();
-
-        // Generate weight ranges and method calls
-        let mut cumulative_weight = 0u32;
-        let weight_ranges: Vec<_> = weighted_methods
-            .iter()
-            .map(|method| {
-                let weight = method.constraints.weight.unwrap();
-                let _start = cumulative_weight;
-                cumulative_weight += weight;
-                let end = cumulative_weight;
-                let method_ident = &method.ident;
-
-                quote! {
-                    if random_weight < #end {
-                        self.#method_ident();
-                        continue;
-                    }
-                }
-            })
-            .collect();
-
-        quote! {
-            // Weighted flow selection based on specified weights
-            for _ in 0..flow_calls_per_iteration {
-                let random_weight = self.trident.random_from_range(0..#total_weight);
-                #(#weight_ranges)*
+    /// Generate the checks of all invariant methods after the given flow, recording
+    /// the checked invariant so a failure is reported with the flow which broke it
+    fn generate_invariant_checks(&self, flow_ident: &Ident) -> TokenStream {
+        let flow_name = flow_ident.to_string();
+        let checks = self.invariant_methods.iter().map(|invariant_method| {
+            let invariant_name = invariant_method.to_string();
+            quote! {
+                self.trident._begin_invariant(#invariant_name, #flow_name);
+                self.#invariant_method();
+                self.trident._end_invariant();
             }
+        });
+        quote! {
+            #(#checks)*
         }
     }
 
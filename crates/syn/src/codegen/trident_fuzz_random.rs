@@ -0,0 +1,158 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use quote::ToTokens;
+use syn::parse_quote;
+
+use crate::types::trident_fuzz_random::FuzzRandomData;
+use crate::types::trident_fuzz_random::FuzzRandomField;
+use crate::types::trident_fuzz_random::FuzzRandomFields;
+use crate::types::trident_fuzz_random::TridentFuzzRandomType;
+
+impl ToTokens for TridentFuzzRandomType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = &self.ident;
+        // Every type parameter has to be random, same as the bounds of the standard derives
+        let mut generics = self.generics.clone();
+        let where_clause = generics.make_where_clause();
+        for param in self.generics.type_params() {
+            let param_ident = &param.ident;
+            where_clause
+                .predicates
+                .push(parse_quote! { #param_ident: FuzzRandom });
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let body = match &self.data {
+            FuzzRandomData::Struct(fields) => generate_construction(quote! { Self }, fields),
+            FuzzRandomData::Enum(variants) => {
+                let variants_count = variants.len();
+                let arms = variants.iter().enumerate().map(|(index, variant)| {
+                    let variant_ident = &variant.ident;
+                    let construction =
+                        generate_construction(quote! { Self::#variant_ident }, &variant.fields);
+                    quote! { #index => #construction, }
+                });
+                quote! {
+                    match trident.random_from_range(0..#variants_count) {
+                        #(#arms)*
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        };
+
+        tokens.extend(quote! {
+            impl #impl_generics FuzzRandom for #ident #ty_generics #where_clause {
+                fn random(trident: &mut Trident) -> Self {
+                    #body
+                }
+            }
+        });
+    }
+}
+
+/// Constructs the struct or the enum variant from random field values
+fn generate_construction(path: TokenStream, fields: &FuzzRandomFields) -> TokenStream {
+    match fields {
+        FuzzRandomFields::Named(fields) => {
+            let values = fields.iter().map(|field| {
+                let field_ident = &field.ident;
+                let value = generate_field_value(field);
+                quote! { #field_ident: #value }
+            });
+            quote! { #path { #(#values),* } }
+        }
+        FuzzRandomFields::Unnamed(fields) => {
+            let values = fields.iter().map(generate_field_value);
+            quote! { #path(#(#values),*) }
+        }
+        FuzzRandomFields::Unit => path,
+    }
+}
+
+fn generate_field_value(field: &FuzzRandomField) -> TokenStream {
+    if let Some(range) = &field.overrides.range {
+        quote! { trident.random_from_range(#range) }
+    } else if let Some(len) = &field.overrides.len {
+        quote! {
+            {
+                let len: usize = trident.random_from_range(#len);
+                FuzzRandomLen::random_with_len(trident, len)
+            }
+        }
+    } else {
+        quote! { FuzzRandom::random(trident) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::DeriveInput;
+
+    use super::*;
+    use crate::parser::trident_fuzz_random::parse_trident_fuzz_random;
+
+    fn generate(input: DeriveInput) -> String {
+        parse_trident_fuzz_random(&input)
+            .unwrap()
+            .to_token_stream()
+            .to_string()
+    }
+
+    #[test]
+    fn test_generic_parameters_are_bound() {
+        let generated = generate(parse_quote! {
+            struct Pair<A, B: Clone> where B: Copy {
+                first: A,
+                second: B,
+            }
+        });
+        let expected = quote! {
+            impl<A, B: Clone> FuzzRandom for Pair<A, B>
+            where
+                B: Copy,
+                A: FuzzRandom,
+                B: FuzzRandom
+        };
+        assert!(generated.starts_with(&expected.to_string()));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let generated = generate(parse_quote! {
+            struct Data {
+                #[fuzz_random(range = 1..=100)]
+                amount: u64,
+                #[fuzz_random(len = 0..4)]
+                memo: Vec<u8>,
+            }
+        });
+        let amount = quote! { amount: trident.random_from_range(1..=100) };
+        let memo = quote! {
+            let len: usize = trident.random_from_range(0..4);
+            FuzzRandomLen::random_with_len(trident, len)
+        };
+        assert!(generated.contains(&amount.to_string()));
+        assert!(generated.contains(&memo.to_string()));
+    }
+
+    #[test]
+    fn test_enum_variant_selection() {
+        let generated = generate(parse_quote! {
+            enum Action {
+                Deposit { amount: u64 },
+                Withdraw(u64),
+                Close,
+            }
+        });
+        let expected = quote! {
+            match trident.random_from_range(0..3usize) {
+                0usize => Self::Deposit { amount: FuzzRandom::random(trident) },
+                1usize => Self::Withdraw(FuzzRandom::random(trident)),
+                2usize => Self::Close,
+                _ => unreachable!(),
+            }
+        };
+        assert!(generated.contains(&expected.to_string()));
+    }
+}
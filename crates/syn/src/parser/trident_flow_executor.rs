@@ -24,20 +24,27 @@ pub fn parse_trident_flow_executor(input: &ItemImpl) -> ParseResult<TridentFlowE
     let generics = input.generics.clone();
 
     let mut init_method = None;
+    let mut init_snapshot = false;
     let mut end_method = None;
     let mut flow_methods = Vec::new();
+    let mut invariant_methods = Vec::new();
 
-    // Collect init, end, and flow methods
+    // Collect init, end, invariant and flow methods
     for item in &input.items {
         if let syn::ImplItem::Fn(method) = item {
             // First check for init methods
-            if method.attrs.iter().any(|attr| attr.path().is_ident("init")) {
+            if let Some(init_attr) = method
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("init"))
+            {
                 if init_method.is_some() {
                     return Err(ParseError::new(
                         method.span(),
                         "Multiple #[init] methods found. Only one is allowed.",
                     ));
                 }
+                init_snapshot = parse_init_snapshot(init_attr)?;
                 init_method = Some(method.sig.ident.clone());
                 continue;
             }
@@ -54,6 +61,22 @@ pub fn parse_trident_flow_executor(input: &ItemImpl) -> ParseResult<TridentFlowE
                 continue;
             }
 
+            // Then check for invariant methods
+            if let Some(invariant_attr) = method
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("invariant"))
+            {
+                if !matches!(invariant_attr.meta, Meta::Path(_)) {
+                    return Err(ParseError::new_spanned(
+                        invariant_attr,
+                        "#[invariant] does not take any arguments",
+                    ));
+                }
+                invariant_methods.push(method.sig.ident.clone());
+                continue;
+            }
+
             // Then check for flow methods
             if let Some(flow_attr) = method
                 .attrs
@@ -70,6 +93,51 @@ pub fn parse_trident_flow_executor(input: &ItemImpl) -> ParseResult<TridentFlowE
         }
     }
 
+    // Validate that guard methods exist
+    for flow_method in &flow_methods {
+        if let Some(guard) = &flow_method.constraints.requires {
+            let guard_exists = input.items.iter().any(
+                |item| matches!(item, syn::ImplItem::Fn(method) if method.sig.ident == *guard),
+            );
+            if !guard_exists {
+                return Err(ParseError::new(
+                    guard.span(),
+                    format!(
+                        "Guard method `{}` required by flow `{}` not found in the impl block",
+                        guard, flow_method.ident
+                    ),
+                ));
+            }
+        }
+    }
+
+    // Validate that flows are ordered after other active flows
+    for flow_method in &flow_methods {
+        for previous in &flow_method.constraints.after {
+            let previous_name = previous.value();
+            if flow_method.ident == previous_name {
+                return Err(ParseError::new(
+                    previous.span(),
+                    format!("Flow `{}` cannot be ordered after itself", previous_name),
+                ));
+            }
+            let previous_exists = flow_methods.iter().any(|method| {
+                !method.constraints.ignore
+                    && method.constraints.weight != Some(0)
+                    && method.ident == previous_name
+            });
+            if !previous_exists {
+                return Err(ParseError::new(
+                    previous.span(),
+                    format!(
+                        "Flow `{}` is ordered after `{}`, which is not an active #[flow] method",
+                        flow_method.ident, previous_name
+                    ),
+                ));
+            }
+        }
+    }
+
     // Validate weight consistency
     let flows_with_weights: Vec<_> = flow_methods
         .iter()
@@ -108,11 +176,33 @@ pub fn parse_trident_flow_executor(input: &ItemImpl) -> ParseResult<TridentFlowE
         impl_block: input.items.clone(),
         flow_methods,
         init_method,
+        init_snapshot,
         end_method,
+        invariant_methods,
         generics,
     })
 }
 
+/// Parses `#[init]` or `#[init(snapshot)]`
+fn parse_init_snapshot(attr: &Attribute) -> ParseResult<bool> {
+    match &attr.meta {
+        Meta::Path(_) => Ok(false),
+        Meta::List(_) => {
+            let mut snapshot = false;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("snapshot") {
+                    snapshot = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported init option"))
+                }
+            })?;
+            Ok(snapshot)
+        }
+        _ => Err(ParseError::new_spanned(attr, "Invalid init attribute")),
+    }
+}
+
 fn parse_flow_constraints(attr: &Attribute) -> ParseResult<FlowConstraints> {
     let mut constraints = FlowConstraints::default();
 
@@ -145,6 +235,45 @@ fn parse_flow_constraints(attr: &Attribute) -> ParseResult<FlowConstraints> {
                             }
                             Ok(())
                         }
+                        "requires" => {
+                            meta.input.parse::<syn::Token![=]>()?;
+                            let guard_lit: syn::LitStr = meta.input.parse()?;
+                            let guard = guard_lit.parse::<syn::Ident>().map_err(|_| {
+                                syn::Error::new(
+                                    guard_lit.span(),
+                                    "requires must be the name of a method returning bool",
+                                )
+                            })?;
+                            constraints.requires = Some(guard);
+                            Ok(())
+                        }
+                        "after" => {
+                            meta.input.parse::<syn::Token![=]>()?;
+                            constraints.after.push(meta.input.parse()?);
+                            Ok(())
+                        }
+                        "once" => {
+                            if constraints.max_calls.is_some() {
+                                return Err(meta.error("once cannot be combined with max_calls"));
+                            }
+                            constraints.max_calls = Some(1);
+                            Ok(())
+                        }
+                        "max_calls" => {
+                            if constraints.max_calls.is_some() {
+                                return Err(meta.error("max_calls cannot be combined with once"));
+                            }
+                            meta.input.parse::<syn::Token![=]>()?;
+                            let max_calls_lit: syn::LitInt = meta.input.parse()?;
+                            let max_calls = max_calls_lit.base10_parse::<u32>()?;
+                            if max_calls == 0 {
+                                return Err(meta.error(
+                                    "max_calls must be greater than 0, use ignore to disable the flow",
+                                ));
+                            }
+                            constraints.max_calls = Some(max_calls);
+                            Ok(())
+                        }
                         _ => Err(meta.error("unsupported flow constraint")),
                     }
                 } else {
@@ -156,3 +285,244 @@ fn parse_flow_constraints(attr: &Attribute) -> ParseResult<FlowConstraints> {
         _ => Err(ParseError::new_spanned(attr, "Invalid flow attribute")),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn parse_error(input: ItemImpl) -> String {
+        match parse_trident_flow_executor(&input) {
+            Ok(_) => panic!("expected a parse error"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_flow_constraints() {
+        let input: ItemImpl = parse_quote! {
+            impl FuzzTest {
+                #[init(snapshot)]
+                fn start(&mut self) {}
+
+                #[flow(weight = 60, max_calls = 3)]
+                fn deposit(&mut self) {}
+
+                #[flow(weight = 40, requires = "has_deposit", after = "deposit", once)]
+                fn withdraw(&mut self) {}
+
+                fn has_deposit(&self) -> bool {
+                    true
+                }
+            }
+        };
+        let parsed = parse_trident_flow_executor(&input).unwrap();
+        assert!(parsed.init_snapshot);
+
+        let deposit = &parsed.flow_methods[0].constraints;
+        assert_eq!(deposit.weight, Some(60));
+        assert_eq!(deposit.max_calls, Some(3));
+        assert!(deposit.requires.is_none() && deposit.after.is_empty());
+
+        let withdraw = &parsed.flow_methods[1].constraints;
+        assert_eq!(withdraw.weight, Some(40));
+        assert_eq!(withdraw.max_calls, Some(1));
+        assert_eq!(withdraw.requires.as_ref().unwrap(), "has_deposit");
+        assert_eq!(withdraw.after[0].value(), "deposit");
+    }
+
+    #[test]
+    fn test_parse_requires() {
+        let input: ItemImpl = parse_quote! {
+            impl FuzzTest {
+                #[flow]
+                fn deposit(&mut self) {}
+
+                #[flow(requires = "has_deposit")]
+                fn withdraw(&mut self) {}
+
+                fn has_deposit(&self) -> bool {
+                    true
+                }
+            }
+        };
+        let parsed = parse_trident_flow_executor(&input).unwrap();
+
+        assert!(parsed.flow_methods[0].constraints.requires.is_none());
+        let guard = parsed.flow_methods[1].constraints.requires.as_ref();
+        assert_eq!(guard.unwrap(), "has_deposit");
+    }
+
+    #[test]
+    fn test_invalid_guard() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(requires = "self.has_deposit()")]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(
+            error,
+            "requires must be the name of a method returning bool"
+        );
+    }
+
+    #[test]
+    fn test_missing_guard() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(requires = "has_deposit")]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(
+            error,
+            "Guard method `has_deposit` required by flow `withdraw` not found in the impl block"
+        );
+    }
+
+    #[test]
+    fn test_unknown_after_target() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(after = "deposit")]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(
+            error,
+            "Flow `withdraw` is ordered after `deposit`, which is not an active #[flow] method"
+        );
+    }
+
+    #[test]
+    fn test_after_inactive_flow() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(ignore)]
+                fn deposit(&mut self) {}
+
+                #[flow(after = "deposit")]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(
+            error,
+            "Flow `withdraw` is ordered after `deposit`, which is not an active #[flow] method"
+        );
+    }
+
+    #[test]
+    fn test_after_itself() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(after = "withdraw")]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(error, "Flow `withdraw` cannot be ordered after itself");
+    }
+
+    #[test]
+    fn test_once_with_max_calls() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(once, max_calls = 2)]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(error, "max_calls cannot be combined with once");
+
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(max_calls = 2, once)]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(error, "once cannot be combined with max_calls");
+    }
+
+    #[test]
+    fn test_zero_max_calls() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(max_calls = 0)]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(
+            error,
+            "max_calls must be greater than 0, use ignore to disable the flow"
+        );
+    }
+
+    #[test]
+    fn test_parse_invariants() {
+        let input: ItemImpl = parse_quote! {
+            impl FuzzTest {
+                #[flow]
+                fn deposit(&mut self) {}
+
+                #[invariant]
+                fn balance_matches(&mut self) {}
+
+                #[invariant]
+                fn supply_is_constant(&mut self) {}
+            }
+        };
+        let parsed = parse_trident_flow_executor(&input).unwrap();
+
+        assert_eq!(parsed.flow_methods.len(), 1);
+        assert_eq!(
+            parsed.invariant_methods,
+            ["balance_matches", "supply_is_constant"]
+        );
+    }
+
+    #[test]
+    fn test_invariant_with_arguments() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[invariant(after = "deposit")]
+                fn balance_matches(&mut self) {}
+            }
+        });
+        assert_eq!(error, "#[invariant] does not take any arguments");
+    }
+
+    #[test]
+    fn test_weight_validation() {
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(weight = 101)]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert_eq!(error, "Weight must be between 0 and 100");
+
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(weight = 100)]
+                fn deposit(&mut self) {}
+
+                #[flow]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert!(error.starts_with("Weight consistency error"));
+        assert!(error.ends_with("Flows without weights: withdraw"));
+
+        let error = parse_error(parse_quote! {
+            impl FuzzTest {
+                #[flow(weight = 50)]
+                fn deposit(&mut self) {}
+
+                #[flow(weight = 40)]
+                fn withdraw(&mut self) {}
+            }
+        });
+        assert!(error
+            .starts_with("Total weight must equal exactly 100: The sum of all flow weights is 90"));
+    }
+}
@@ -0,0 +1,223 @@
+use syn::parse::Error as ParseError;
+use syn::parse::Result as ParseResult;
+use syn::spanned::Spanned;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+
+use crate::types::trident_fuzz_random::FuzzRandomData;
+use crate::types::trident_fuzz_random::FuzzRandomField;
+use crate::types::trident_fuzz_random::FuzzRandomFields;
+use crate::types::trident_fuzz_random::FuzzRandomOverrides;
+use crate::types::trident_fuzz_random::FuzzRandomVariant;
+use crate::types::trident_fuzz_random::TridentFuzzRandomType;
+
+pub fn parse_trident_fuzz_random(input: &DeriveInput) -> ParseResult<TridentFuzzRandomType> {
+    let data = match &input.data {
+        Data::Struct(data) => FuzzRandomData::Struct(parse_fields(&data.fields)?),
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(ParseError::new(
+                    input.span(),
+                    "FuzzRandom cannot be derived for enums without variants",
+                ));
+            }
+            let variants = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    Ok(FuzzRandomVariant {
+                        ident: variant.ident.clone(),
+                        fields: parse_fields(&variant.fields)?,
+                    })
+                })
+                .collect::<ParseResult<Vec<_>>>()?;
+            FuzzRandomData::Enum(variants)
+        }
+        Data::Union(_) => {
+            return Err(ParseError::new(
+                input.span(),
+                "FuzzRandom can only be derived for structs and enums",
+            ))
+        }
+    };
+
+    Ok(TridentFuzzRandomType {
+        ident: input.ident.clone(),
+        generics: input.generics.clone(),
+        data,
+    })
+}
+
+fn parse_fields(fields: &Fields) -> ParseResult<FuzzRandomFields> {
+    let parse = |fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>| {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(FuzzRandomField {
+                    ident: field.ident.clone(),
+                    overrides: parse_overrides(&field.attrs)?,
+                })
+            })
+            .collect::<ParseResult<Vec<_>>>()
+    };
+    match fields {
+        Fields::Named(fields) => Ok(FuzzRandomFields::Named(parse(&fields.named)?)),
+        Fields::Unnamed(fields) => Ok(FuzzRandomFields::Unnamed(parse(&fields.unnamed)?)),
+        Fields::Unit => Ok(FuzzRandomFields::Unit),
+    }
+}
+
+/// Parses `#[fuzz_random(range = ..)]` and `#[fuzz_random(len = ..)]`
+fn parse_overrides(attrs: &[Attribute]) -> ParseResult<FuzzRandomOverrides> {
+    let mut overrides = FuzzRandomOverrides::default();
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("fuzz_random"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                if overrides.range.is_some() {
+                    return Err(meta.error("range is specified more than once"));
+                }
+                overrides.range = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("len") {
+                if overrides.len.is_some() {
+                    return Err(meta.error("len is specified more than once"));
+                }
+                overrides.len = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported fuzz_random option, expected range or len"))
+            }
+        })?;
+    }
+
+    if let (Some(range), Some(_)) = (&overrides.range, &overrides.len) {
+        return Err(ParseError::new_spanned(
+            range,
+            "range and len cannot be combined, range applies to numbers and len to collections",
+        ));
+    }
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    fn parse_error(input: DeriveInput) -> String {
+        match parse_trident_fuzz_random(&input) {
+            Ok(_) => panic!("expected a parse error"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        let input: DeriveInput = parse_quote! {
+            struct Data {
+                #[fuzz_random(range = 1..=100)]
+                amount: u64,
+                #[fuzz_random(len = 0..4)]
+                memo: Vec<u8>,
+                owner: Pubkey,
+            }
+        };
+        let parsed = parse_trident_fuzz_random(&input).unwrap();
+
+        let FuzzRandomData::Struct(FuzzRandomFields::Named(fields)) = parsed.data else {
+            panic!("expected a struct with named fields");
+        };
+        assert!(fields[0].overrides.range.is_some() && fields[0].overrides.len.is_none());
+        assert!(fields[1].overrides.range.is_none() && fields[1].overrides.len.is_some());
+        assert!(fields[2].overrides.range.is_none() && fields[2].overrides.len.is_none());
+    }
+
+    #[test]
+    fn test_parse_enum_variants() {
+        let input: DeriveInput = parse_quote! {
+            enum Action {
+                Deposit { #[fuzz_random(range = 1..10)] amount: u64 },
+                Withdraw(u64),
+                Close,
+            }
+        };
+        let parsed = parse_trident_fuzz_random(&input).unwrap();
+
+        let FuzzRandomData::Enum(variants) = parsed.data else {
+            panic!("expected an enum");
+        };
+        let idents: Vec<_> = variants.iter().map(|v| v.ident.to_string()).collect();
+        assert_eq!(idents, ["Deposit", "Withdraw", "Close"]);
+        assert!(matches!(variants[1].fields, FuzzRandomFields::Unnamed(_)));
+        assert!(matches!(variants[2].fields, FuzzRandomFields::Unit));
+    }
+
+    #[test]
+    fn test_duplicate_range() {
+        let error = parse_error(parse_quote! {
+            struct Data {
+                #[fuzz_random(range = 1..10, range = 2..20)]
+                amount: u64,
+            }
+        });
+        assert_eq!(error, "range is specified more than once");
+    }
+
+    #[test]
+    fn test_range_with_len() {
+        let error = parse_error(parse_quote! {
+            struct Data {
+                #[fuzz_random(range = 1..10)]
+                #[fuzz_random(len = 1..10)]
+                memo: Vec<u8>,
+            }
+        });
+        assert!(error.starts_with("range and len cannot be combined"));
+    }
+
+    #[test]
+    fn test_unsupported_option() {
+        let error = parse_error(parse_quote! {
+            struct Data {
+                #[fuzz_random(max = 10)]
+                amount: u64,
+            }
+        });
+        assert_eq!(
+            error,
+            "unsupported fuzz_random option, expected range or len"
+        );
+    }
+
+    #[test]
+    fn test_union() {
+        let error = parse_error(parse_quote! {
+            union Data {
+                amount: u64,
+                bytes: [u8; 8],
+            }
+        });
+        assert_eq!(
+            error,
+            "FuzzRandom can only be derived for structs and enums"
+        );
+    }
+
+    #[test]
+    fn test_enum_without_variants() {
+        let error = parse_error(parse_quote! {
+            enum Data {}
+        });
+        assert_eq!(
+            error,
+            "FuzzRandom cannot be derived for enums without variants"
+        );
+    }
+}
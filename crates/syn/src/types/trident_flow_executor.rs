@@ -6,7 +6,12 @@ use syn::ImplItem;
 pub struct FlowConstraints {
     pub ignore: bool,
     pub weight: Option<u32>,
-    // Future fields can be added here (e.g., weight: Option<u32>)
+    /// Guard method which has to return `true` for the flow to be selected
+    pub requires: Option<Ident>,
+    /// Flows which have to be called earlier in the iteration
+    pub after: Vec<syn::LitStr>,
+    /// Maximal number of calls per iteration, `once` sets it to 1
+    pub max_calls: Option<u32>,
 }
 
 pub struct FlowMethod {
@@ -19,6 +24,10 @@ pub struct TridentFlowExecutorImpl {
     pub impl_block: Vec<ImplItem>,
     pub flow_methods: Vec<FlowMethod>,
     pub init_method: Option<Ident>,
+    /// Restore the state after the init method instead of executing it, `#[init(snapshot)]`
+    pub init_snapshot: bool,
     pub end_method: Option<Ident>,
+    /// Methods checked after the `#[init]` method and after every flow call
+    pub invariant_methods: Vec<Ident>,
     pub generics: Generics,
 }
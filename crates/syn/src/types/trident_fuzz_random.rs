@@ -0,0 +1,40 @@
+use syn::Expr;
+use syn::Generics;
+use syn::Ident;
+
+/// Per-field overrides of `#[fuzz_random(...)]`
+#[derive(Default)]
+pub struct FuzzRandomOverrides {
+    /// Range of a numeric field, `#[fuzz_random(range = 1..=100)]`
+    pub range: Option<Expr>,
+    /// Range of the length of a collection field, `#[fuzz_random(len = 0..=4)]`
+    pub len: Option<Expr>,
+}
+
+pub struct FuzzRandomField {
+    /// `None` for fields of tuple structs and tuple variants
+    pub ident: Option<Ident>,
+    pub overrides: FuzzRandomOverrides,
+}
+
+pub enum FuzzRandomFields {
+    Named(Vec<FuzzRandomField>),
+    Unnamed(Vec<FuzzRandomField>),
+    Unit,
+}
+
+pub struct FuzzRandomVariant {
+    pub ident: Ident,
+    pub fields: FuzzRandomFields,
+}
+
+pub enum FuzzRandomData {
+    Struct(FuzzRandomFields),
+    Enum(Vec<FuzzRandomVariant>),
+}
+
+pub struct TridentFuzzRandomType {
+    pub ident: Ident,
+    pub generics: Generics,
+    pub data: FuzzRandomData,
+}
@@ -0,0 +1,279 @@
+use convert_case::Case;
+use convert_case::Casing;
+use serde_json::json;
+use trident_idl_spec::Idl;
+use trident_idl_spec::IdlField;
+use trident_idl_spec::IdlInstruction;
+use trident_idl_spec::IdlInstructionAccount;
+use trident_idl_spec::IdlInstructionAccountItem;
+use trident_idl_spec::IdlPda;
+use trident_idl_spec::IdlSeed;
+use trident_idl_spec::IdlType;
+
+use crate::TridentTemplates;
+
+impl TridentTemplates {
+    /// Build one flow per instruction of every program (preserving IDL order)
+    pub(crate) fn build_flows(&self, idls: &[Idl]) -> Vec<serde_json::Value> {
+        let mut flows = Vec::new();
+
+        for idl in idls {
+            let program_name = if idl.metadata.name.is_empty() {
+                "unknown_program"
+            } else {
+                &idl.metadata.name
+            };
+            let module_name = program_name.to_case(Case::Snake);
+
+            for instruction in &idl.instructions {
+                let snake_name = instruction.name.to_case(Case::Snake);
+                // Instructions of different programs may share the name
+                let name = if idls.len() > 1 {
+                    format!("flow_{}_{}", module_name, snake_name)
+                } else {
+                    format!("flow_{}", snake_name)
+                };
+
+                flows.push(json!({
+                    "name": name,
+                    "log_as": instruction.name,
+                    "module_name": module_name,
+                    "camel_name": instruction.name.to_case(Case::UpperCamel),
+                    "accounts": self.build_flow_accounts(instruction, &module_name),
+                    "instruction_accounts": self.build_instruction_accounts(
+                        &instruction.accounts,
+                        &module_name,
+                        &instruction.name.to_case(Case::UpperCamel),
+                    ),
+                }));
+            }
+        }
+
+        flows
+    }
+
+    /// Resolve the accounts of the instruction in the order they have to be obtained,
+    /// PDAs are derived after the accounts their seeds refer to
+    fn build_flow_accounts(
+        &self,
+        instruction: &IdlInstruction,
+        module_name: &str,
+    ) -> Vec<serde_json::Value> {
+        let mut accounts = Vec::new();
+        self.flatten_accounts(&instruction.accounts, &mut accounts);
+
+        let mut resolved: Vec<&IdlInstructionAccount> = Vec::new();
+        let mut flow_accounts = Vec::new();
+        let mut pending = Vec::new();
+
+        for account in accounts.iter().copied() {
+            if account.address.is_some() || resolved.iter().any(|a| a.name == account.name) {
+                continue;
+            }
+            if account.pda.is_some() {
+                pending.push(account);
+            } else {
+                flow_accounts.push(json!({
+                    "name": account.name,
+                    "is_signer": account.signer,
+                    "seeds": null
+                }));
+                resolved.push(account);
+            }
+        }
+
+        // Seeds may refer to other PDAs, derive them once those are resolved
+        loop {
+            let mut progress = false;
+            pending.retain(|account| {
+                let pda = account.pda.as_ref().unwrap();
+                match self.pda_seeds(pda, &instruction.args, &accounts, &resolved, module_name) {
+                    Some((seeds, program_id)) => {
+                        flow_accounts.push(json!({
+                            "name": account.name,
+                            "is_signer": account.signer,
+                            "seeds": seeds,
+                            "program_id": program_id
+                        }));
+                        resolved.push(account);
+                        progress = true;
+                        false
+                    }
+                    None => true,
+                }
+            });
+            if !progress {
+                break;
+            }
+        }
+
+        // Seeds which cannot be derived (e.g. fields of account data) are left to the user
+        for account in pending {
+            flow_accounts.push(json!({
+                "name": account.name,
+                "is_signer": account.signer,
+                "seeds": null,
+                "unresolved_seeds": true
+            }));
+        }
+
+        flow_accounts
+    }
+
+    /// Collect single accounts, including the accounts of composite accounts (deduplicated by name)
+    #[allow(clippy::only_used_in_recursion)]
+    fn flatten_accounts<'a>(
+        &self,
+        accounts: &'a [IdlInstructionAccountItem],
+        acc: &mut Vec<&'a IdlInstructionAccount>,
+    ) {
+        for account in accounts {
+            match account {
+                IdlInstructionAccountItem::Single(a) => {
+                    if !acc.iter().any(|existing| existing.name == a.name) {
+                        acc.push(a);
+                    }
+                }
+                IdlInstructionAccountItem::Composite(c) => {
+                    self.flatten_accounts(&c.accounts, acc);
+                }
+            }
+        }
+    }
+
+    /// Convert PDA seeds to Rust expressions, `None` if any seed cannot be derived
+    fn pda_seeds(
+        &self,
+        pda: &IdlPda,
+        args: &[IdlField],
+        accounts: &[&IdlInstructionAccount],
+        resolved: &[&IdlInstructionAccount],
+        module_name: &str,
+    ) -> Option<(Vec<String>, String)> {
+        let seeds = pda
+            .seeds
+            .iter()
+            .map(|seed| match seed {
+                IdlSeed::Const(const_seed) => Some(self.const_seed(&const_seed.value)),
+                IdlSeed::Account(account_seed) => Some(format!(
+                    "{}.as_ref()",
+                    self.account_seed(&account_seed.path, accounts, resolved)?
+                )),
+                IdlSeed::Arg(arg_seed) => self.arg_seed(&arg_seed.path, args),
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let program_id = match &pda.program {
+            None => format!("{}::program_id()", module_name),
+            Some(IdlSeed::Const(const_seed)) if const_seed.value.len() == 32 => format!(
+                "Pubkey::new_from_array([{}])",
+                const_seed
+                    .value
+                    .iter()
+                    .map(|b| format!("{}u8", b))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Some(IdlSeed::Account(account_seed)) => {
+                self.account_seed(&account_seed.path, accounts, resolved)?
+            }
+            Some(_) => return None,
+        };
+
+        Some((seeds, program_id))
+    }
+
+    /// Constant seed as a byte string literal if printable, otherwise as a byte array
+    fn const_seed(&self, value: &[u8]) -> String {
+        if !value.is_empty()
+            && value
+                .iter()
+                .all(|b| b.is_ascii_graphic() && *b != b'"' && *b != b'\\')
+        {
+            format!("b\"{}\"", String::from_utf8_lossy(value))
+        } else {
+            format!(
+                "&[{}]",
+                value
+                    .iter()
+                    .map(|b| format!("{}u8", b))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    }
+
+    /// Address of the account the seed refers to
+    fn account_seed(
+        &self,
+        path: &str,
+        accounts: &[&IdlInstructionAccount],
+        resolved: &[&IdlInstructionAccount],
+    ) -> Option<String> {
+        // Paths into account data cannot be derived before the transaction
+        if path.contains('.') {
+            return None;
+        }
+        let account = accounts.iter().find(|a| a.name == path)?;
+        match &account.address {
+            Some(address) => Some(format!("pubkey!(\"{}\")", address)),
+            None => resolved
+                .iter()
+                .any(|a| a.name == path)
+                .then(|| path.to_string()),
+        }
+    }
+
+    /// Bytes of the instruction argument the seed refers to
+    fn arg_seed(&self, path: &str, args: &[IdlField]) -> Option<String> {
+        let arg = args.iter().find(|arg| arg.name == path)?;
+        let field = format!("data.{}", arg.name);
+        match &arg.ty {
+            IdlType::U8
+            | IdlType::I8
+            | IdlType::U16
+            | IdlType::I16
+            | IdlType::U32
+            | IdlType::I32
+            | IdlType::U64
+            | IdlType::I64
+            | IdlType::U128
+            | IdlType::I128 => Some(format!("&{}.to_le_bytes()", field)),
+            IdlType::Bool => Some(format!("&[{} as u8]", field)),
+            IdlType::Pubkey | IdlType::PublicKey => Some(format!("{}.as_ref()", field)),
+            IdlType::String => Some(format!("{}.as_bytes()", field)),
+            IdlType::Bytes => Some(format!("{}.as_slice()", field)),
+            IdlType::Vec(inner) | IdlType::Array(inner, _) if matches!(**inner, IdlType::U8) => {
+                Some(format!("{}.as_slice()", field))
+            }
+            _ => None,
+        }
+    }
+
+    /// Expression constructing the instruction accounts from the resolved accounts
+    #[allow(clippy::only_used_in_recursion)]
+    fn build_instruction_accounts(
+        &self,
+        accounts: &[IdlInstructionAccountItem],
+        module_name: &str,
+        camel_name: &str,
+    ) -> String {
+        let arguments = accounts
+            .iter()
+            .filter_map(|account| match account {
+                IdlInstructionAccountItem::Single(a) => a.address.is_none().then(|| a.name.clone()),
+                IdlInstructionAccountItem::Composite(c) => Some(self.build_instruction_accounts(
+                    &c.accounts,
+                    module_name,
+                    &c.name.to_case(Case::UpperCamel),
+                )),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(
+            "{}::{}InstructionAccounts::new({})",
+            module_name, camel_name, arguments
+        )
+    }
+}
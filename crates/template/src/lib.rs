@@ -14,6 +14,7 @@ use trident_idl_spec::IdlTypeDefTy;
 use crate::error::TemplateError;
 
 pub mod error;
+mod flows;
 
 /// Simple template engine for Trident code generation
 pub struct TridentTemplates {
@@ -46,17 +47,27 @@ impl TridentTemplates {
     }
 
     /// Generate all templates from IDLs
+    ///
+    /// With `generate_flows`, the fuzz test contains one flow per instruction
+    /// instead of empty flows.
     pub fn generate(
         &self,
         idls: &[Idl],
         trident_version: &str,
+        generate_flows: bool,
     ) -> Result<GeneratedFiles, TemplateError> {
         let programs_data = self.build_programs_with_instructions_data(idls)?;
+        let flows = if generate_flows {
+            self.build_flows(idls)
+        } else {
+            Vec::new()
+        };
 
         // Generate files
-        let test_fuzz = self
-            .tera
-            .render("test_fuzz.rs", &Context::from_serialize(json!({}))?)?;
+        let test_fuzz = self.tera.render(
+            "test_fuzz.rs",
+            &Context::from_serialize(json!({"flows": flows}))?,
+        )?;
         let fuzz_accounts = self.tera.render(
             "fuzz_accounts.rs",
             &Context::from_serialize(json!({"accounts": self.collect_all_accounts(idls)}))?,